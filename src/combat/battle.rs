@@ -1,23 +1,63 @@
 use super::inventory::battle_menu;
 use crate::{
-    combat::enemy::{Data, Rewards},
-    data::{inventory::equipment::Equipment, player::Player, xp::XP},
-    prelude::{confirm, page_header, pause, random_num, select, sleep, unreachable, Instructions},
+    combat::enemy::{Data, Enemies, Rewards},
+    data::{
+        classes::{self, Ability, Skill},
+        consumables,
+        health::{BATTLE_HUNGER, POISON_ROUNDS},
+        inventory::{
+            equipment::{Equipment, Slot},
+            weight,
+        },
+        player::Player,
+        quests::Quests,
+        xp::XP,
+    },
+    prelude::{confirm, page_header, pause, random_num, select, sleep, success, unreachable, Instructions},
     utils::terminal::STANDARD_SLEEP,
 };
 
+/**
+The result of a finished battle, returned to the caller so it can decide
+what happens next.
+
+- `Victory`: Every enemy was defeated. Contains all rewards looted.
+- `Defeat`: The player lost and has already been revived (or reset in hardmode).
+- `Retreat`: The player fled before defeating any enemy.
+- `FloorReached`: The player fled a looped battle after clearing some floors.
+*/
+#[derive(Debug, Clone)]
+pub enum BattleOutcome {
+    Victory(Vec<Rewards>),
+    Defeat,
+    Retreat,
+    FloorReached(usize),
+}
+
+/// Result of a single round of player input.
+enum Turn {
+    Continue,
+    Won,
+    Lost,
+    Retreated,
+}
+
 /**
 Dynamic battle generator/handler.
 
 # Example
 
 ```ignore
-use albion_terminal_rpg::combat::battle::Battle;
+use albion_terminal_rpg::combat::battle::{Battle, BattleOutcome};
 use albion_terminal_rpg::data::player::Player;
 
 let mut player = Player::default();
 
-Battle::new("The Stronghold", "You delve into the Stronghold...", &mut player, 50, None).start();
+let outcome = Battle::new("The Stronghold", "You delve into the Stronghold...", &mut player, 50).start();
+
+if let BattleOutcome::Victory(rewards) = outcome {
+    println!("Looted {} rewards.", rewards.len());
+}
 ```
 */
 pub struct Battle<'a> {
@@ -27,10 +67,14 @@ pub struct Battle<'a> {
     pub enemy: Data,
     pub loops: usize,
     pub floor: usize,
-    pub is_first_battle: bool,
     pub is_looped: bool,
     pub pause_seconds: u64,
-    pub end_function: Option<fn(&mut Player)>,
+
+    /// Abilities already used against the current enemy.
+    pub used_abilities: Vec<Skill>,
+
+    /// Whether the enemy's next attack will be negated.
+    pub shielded: bool,
 }
 
 /**
@@ -40,13 +84,7 @@ Entrypoint and Main Battle Menu
 */
 impl<'a> Battle<'a> {
     /// Instantiate a new battle
-    pub fn new(
-        title: &'static str,
-        prompt: &'static str,
-        player: &'a mut Player,
-        loops: usize,
-        exit_function: Option<fn(&mut Player)>,
-    ) -> Self {
+    pub fn new(title: &'static str, prompt: &'static str, player: &'a mut Player, loops: usize) -> Self {
         Self {
             header: title,
             prompt,
@@ -54,83 +92,162 @@ impl<'a> Battle<'a> {
             player,
             loops,
             floor: 0,
-            is_first_battle: true,
             is_looped: loops > 0,
             pause_seconds: STANDARD_SLEEP,
-            end_function: exit_function,
+            used_abilities: vec![],
+            shielded: false,
         }
     }
 
-    /// This is the function caled to begin the battle sequence after the battle
-    /// settings have been set.
-    pub fn start(&mut self) {
+    /// This is the function called to begin the battle sequence after the battle
+    /// settings have been set. Runs every fight in order and returns how the
+    /// battle ended.
+    pub fn start(&mut self) -> BattleOutcome {
         // Prelude
         page_header(self.header, &Instructions::None);
         Equipment::check_equipment_ownership(self.player);
 
-        if self.player.equipment.armor.is_none() || self.player.equipment.weapon.is_none() {
+        if !Equipment::has_armor(self.player, Slot::Chest) || !Equipment::has_weapon(self.player) {
             let confirm = confirm("Are you sure you want to fight without equipment? You'll die.");
 
             if !confirm {
                 println!("Returning home.");
                 pause();
 
-                crate::menus::game_menu::main(self.player);
+                return BattleOutcome::Retreat;
             }
         }
 
+        if weight::is_encumbered(self.player) {
+            let confirm = confirm("You are carrying too much and will only deal half damage. Fight anyway?");
+
+            if !confirm {
+                println!("Returning home.");
+                pause();
+
+                return BattleOutcome::Retreat;
+            }
+        }
+
+        let mut looted: Vec<Rewards> = vec![];
+
+        loop {
+            self.encounter();
+
+            match self.player_actions() {
+                Turn::Won => {
+                    looted.extend(self.victory());
+
+                    if self.is_looped {
+                        self.player.statistics.clear_floor(self.floor);
+                    }
+
+                    if self.loops == 0 {
+                        return BattleOutcome::Victory(looted);
+                    }
+                }
+                Turn::Lost => {
+                    self.defeat();
+                    return BattleOutcome::Defeat;
+                }
+                Turn::Retreated => {
+                    self.retreat();
+                    self.player.statistics.retreats += 1;
+
+                    let floors_cleared = self.floor.saturating_sub(1);
+
+                    if self.is_looped && floors_cleared > 0 {
+                        return BattleOutcome::FloorReached(floors_cleared);
+                    }
+
+                    return BattleOutcome::Retreat;
+                }
+                Turn::Continue => unreachable(),
+            }
+        }
+    }
+
+    /// Advance to the next floor (if looped) and introduce the enemy.
+    fn encounter(&mut self) {
+        page_header(self.header, &Instructions::None);
+
         if self.loops > 0 {
             self.floor += 1;
             self.loops -= 1;
         }
 
+        // Every fight takes an hour of in-game time and makes the player hungrier
+        self.player.pass_time(1);
+        self.player.health.hunger_up(BATTLE_HUNGER);
+
         println!("{}", self.prompt);
         sleep(self.pause_seconds);
 
-        if self.is_first_battle {
-            self.is_first_battle = false; // generate new enemy for subsequent battles
-        } else {
+        // The first enemy is generated by `Battle::new`
+        if self.floor > 1 {
             self.enemy = Data::new(self.player.xp.combat, self.player.health.hp);
         }
 
+        self.used_abilities.clear();
+        self.shielded = false;
+
         println!();
         println!("You are now fighting a {}.", self.enemy.name);
         sleep(self.pause_seconds);
-
-        self.player_actions();
     }
 
-    /// Player can either attack, manage inventory, or retreat.
-    fn player_actions(&mut self) {
-        page_header(
-            format!("{} - {}", self.header, self.enemy.name),
-            &Instructions::Keyboard,
-        );
-
-        if self.is_looped {
-            println!("Floor: {}", self.floor);
-            println!("Floors Left: {}", self.loops);
-            println!();
-        }
-
-        println!("{}", self.enemy);
+    /// Player can either attack, use an ability, manage inventory, or retreat until the fight is decided.
+    fn player_actions(&mut self) -> Turn {
+        loop {
+            page_header(
+                format!("{} - {}", self.header, self.enemy.name),
+                &Instructions::Keyboard,
+            );
+
+            if self.is_looped {
+                println!("Floor: {}", self.floor);
+                println!("Floors Left: {}", self.loops);
+                println!();
+            }
 
-        println!("Player HP: {}", self.player.health.hp);
-        println!("Player Hunger: {}", self.player.health.hunger);
-        println!();
+            println!("{}", self.enemy);
 
-        let attack_string = &format!("1. Attack the {}", self.enemy.name);
+            println!("Player HP: {}/{}", self.player.health.hp, self.player.max_hp());
+            println!(
+                "Player Hunger: {} ({})",
+                self.player.health.hunger,
+                self.player.health.status()
+            );
 
-        let action = select(&[attack_string.as_str(), "2. Inventory", "3. Retreat"], None);
+            if self.player.health.poison > 0 {
+                println!("Poisoned: {} rounds left", self.player.health.poison);
+            }
+            println!();
 
-        match action {
-            0 => self.attack_sequence(),
-            1 => {
-                battle_menu(self.player);
-                self.player_actions();
+            let attack_string = &format!("1. Attack the {}", self.enemy.name);
+
+            let action = select(
+                &[attack_string.as_str(), "2. Abilities", "3. Inventory", "4. Retreat"],
+                None,
+            );
+
+            let turn = match action {
+                0 => self.attack_sequence(None),
+                1 => self.abilities(),
+                2 => {
+                    battle_menu(self.player);
+                    Turn::Continue
+                }
+                3 => Turn::Retreated,
+                _ => {
+                    unreachable();
+                    Turn::Continue
+                }
+            };
+
+            if !matches!(turn, Turn::Continue) {
+                return turn;
             }
-            2 => self.retreat(),
-            _ => unreachable(),
         }
     }
 }
@@ -141,45 +258,170 @@ Attacking Sequences
 --------------------------------------------------------------------------------
 */
 impl Battle<'_> {
-    /// Player attacks first, enemy attacks second, player heals, repeat.
-    fn attack_sequence(&mut self) {
+    /// Pick an unlocked class ability to use. Each ability can be used once per enemy.
+    fn abilities(&mut self) -> Turn {
+        page_header(format!("{} - Abilities", self.header), &Instructions::Keyboard);
+
+        let skills: Vec<Skill> = classes::abilities(self.player)
+            .into_iter()
+            .filter(|skill| !self.used_abilities.contains(skill))
+            .collect();
+
+        if skills.is_empty() {
+            println!("You have no abilities left to use against the {}.", self.enemy.name);
+            pause();
+            return Turn::Continue;
+        }
+
+        let mut choices: Vec<String> = skills
+            .iter()
+            .map(|skill| format!("{skill}: {}", skill.effect()))
+            .collect();
+        choices.push("NAV: Go Back".to_string());
+
+        let Some(skill) = skills.get(select(&choices, None)).copied() else {
+            return Turn::Continue;
+        };
+
+        self.used_abilities.push(skill);
+        self.attack_sequence(Some(skill))
+    }
+
+    /// Player attacks (or uses an ability) first, enemy attacks second, player heals, repeat.
+    fn attack_sequence(&mut self, skill: Option<Skill>) -> Turn {
         page_header(self.header, &Instructions::None);
 
-        self.player_turn();
+        if let Some(skill) = skill {
+            println!("You use {skill}!");
+        }
+
+        if self.player_turn(skill.and_then(Skill::ability)) {
+            return Turn::Won;
+        }
 
         println!();
 
-        self.enemy_turn();
+        if self.enemy_turn() {
+            return Turn::Lost;
+        }
 
         println!();
 
-        self.player.health.heal();
+        let max_hp: usize = self.player.max_hp();
+        self.player.health.heal(max_hp);
+
+        let drained: usize = self.player.health.starve();
+
+        if drained > 0 {
+            println!("You are starving and lost {drained} hp.");
+        }
+
+        let poisoned: usize = self.player.health.poisoned();
+
+        if poisoned > 0 {
+            println!("The poison drains {poisoned} hp.");
+        }
+
+        self.player.statistics.damage_taken += drained + poisoned;
 
         println!();
 
         pause();
 
-        self.player_actions();
+        Turn::Continue
     }
 
-    /// Player attacks the enemy.
-    fn player_turn(&mut self) {
-        let enemy_type = &self.enemy.name;
+    /// Player attacks the enemy, optionally with an ability. Returns true if the enemy was defeated.
+    fn player_turn(&mut self, ability: Option<Ability>) -> bool {
+        let enemy_type = self.enemy.name.clone();
+
+        match ability {
+            Some(Ability::Heal(percent)) => {
+                let max_hp: usize = self.player.max_hp();
+                let healed: usize = (max_hp * percent / 100).min(max_hp.saturating_sub(self.player.health.hp));
+
+                self.player.health.hp += healed;
+                println!("You recovered {healed} hp.");
+                sleep(self.pause_seconds);
+                return false;
+            }
+            Some(Ability::Shield) => {
+                self.shielded = true;
+                println!("You brace for the {enemy_type}'s next attack.");
+                sleep(self.pause_seconds);
+                return false;
+            }
+            _ => {}
+        }
 
         println!("You attack the {enemy_type}...");
         sleep(self.pause_seconds);
 
-        if !Self::hit() || self.player.equipment.weapon.is_none() {
+        let missed: bool = match ability {
+            Some(Ability::Spell(_)) => false,
+            Some(Ability::Strike { sure_hit: true, .. }) => !Equipment::has_weapon(self.player),
+            _ => random_num(1, 100) > self.player.hit_chance() || !Equipment::has_weapon(self.player),
+        };
+
+        if missed {
             println!("You missed the {enemy_type}.");
             sleep(self.pause_seconds);
-            return;
+            return false;
+        }
+
+        let mut damage: usize = if let Some(Ability::Spell(base)) = ability {
+            base + self.player.attributes.intelligence * 3
+        } else {
+            self.weapon_damage()
+                + Equipment::bonus_damage(self.player)
+                + self.player.attributes.bonus_damage()
+                + consumables::bonus_damage(self.player)
+        };
+
+        if let Some(Ability::Strike { percent, .. }) = ability {
+            damage = damage * percent / 100;
+        }
+
+        if self.player.health.damage_percent() < 100 {
+            damage = damage * self.player.health.damage_percent() / 100;
+            println!("Hunger weakens your attack.");
         }
 
+        if weight::is_encumbered(self.player) && !matches!(ability, Some(Ability::Spell(_))) {
+            damage /= 2;
+            println!("Your heavy load slows your swing.");
+        }
+
+        let critical_chance: usize = self.player.loot.critical_chance() + classes::bonus_critical_chance(self.player);
+
+        if random_num(1, 100) <= critical_chance {
+            damage *= 2;
+            println!("Critical hit!");
+        }
+
+        println!("You hit the {enemy_type} for {damage} damage!");
+
+        let lifesteal: usize = damage * self.player.loot.lifesteal() / 100;
+
+        if lifesteal > 0 {
+            self.player.health.hp = (self.player.health.hp + lifesteal).min(self.player.max_hp());
+            println!("You drained {lifesteal} hp from the {enemy_type}.");
+        }
+
+        self.player.statistics.damage_dealt += damage.min(self.enemy.hp);
+
+        let enemy_defeated: bool = self.enemy.hp <= damage;
+        self.enemy.hp = self.enemy.hp.saturating_sub(damage);
+
+        sleep(self.pause_seconds);
+        enemy_defeated
+    }
+
+    /// Damage of the equipped weapon, wearing it down as it is used.
+    fn weapon_damage(&mut self) -> usize {
         if let Some(equipped_weapon) = &self.player.equipment.weapon {
             let weapon = self.player.weapons.get(equipped_weapon);
-            let damage = weapon.damage;
-
-            println!("You hit the {enemy_type} for {damage} damage!");
+            let damage = weapon.total_damage();
 
             weapon.decrease_durability();
 
@@ -187,48 +429,63 @@ impl Battle<'_> {
                 Equipment::unequip_weapon(self.player, false);
             }
 
-            if self.enemy.hp < damage {
-                self.victory();
-            } else {
-                self.enemy.hp -= damage;
+            return damage;
+        }
+
+        if let Some(weapon) = self.player.loot.weapon() {
+            let damage = weapon.damage;
+            let id = weapon.id;
+
+            if weapon.decrease_durability() {
+                self.player.loot.remove(id);
             }
+
+            return damage;
         }
 
-        sleep(self.pause_seconds);
+        0
     }
 
-    /// Enemy attacks the player
-    fn enemy_turn(&mut self) {
+    /// Enemy attacks the player. Returns true if the player was defeated.
+    fn enemy_turn(&mut self) -> bool {
         let enemy_type = &self.enemy.name;
         let mut damage: usize = self.enemy.damage;
+        let mut player_defeated = false;
 
-        if let Some(equipped_armor) = &self.player.equipment.armor {
-            let armor = self.player.armor.get(equipped_armor);
+        let defense: usize = Equipment::defense(self.player)
+            + self.player.attributes.bonus_defense()
+            + classes::bonus_defense(self.player)
+            + consumables::bonus_defense(self.player);
 
-            if damage > armor.defense {
-                damage -= armor.defense;
-            } else {
-                damage = 0;
-            }
-
-            armor.decrease_durability();
-
-            if !armor.owns {
-                Equipment::unequip_armor(self.player, false);
-            }
+        if damage > defense {
+            damage -= defense;
+        } else {
+            damage = 0;
         }
 
+        Equipment::wear_armor(self.player);
+
         println!("The {enemy_type} attacks you...");
         sleep(self.pause_seconds);
 
-        if Self::hit() && damage > 0 {
+        if self.shielded {
+            self.shielded = false;
+            println!("Your guard held, and the {enemy_type}'s attack was negated!");
+        } else if Self::hit() && damage > 0 {
             println!("The {enemy_type} hit you for {damage} damage!!");
+            self.player.statistics.damage_taken += damage.min(self.player.health.hp);
 
-            if self.player.health.hp < damage {
-                self.defeat();
+            if self.player.health.hp <= damage {
+                self.player.health.hp = 0;
+                player_defeated = true;
             } else {
                 self.player.health.hp -= damage;
             }
+
+            if !player_defeated && self.enemy.flag.is_some_and(Enemies::is_venomous) && random_num(1, 3) == 1 {
+                self.player.health.poison = POISON_ROUNDS;
+                println!("The {enemy_type} poisoned you!");
+            }
         } else if damage == 0 {
             println!("The {enemy_type} hit but the damage was negated by your armor!");
         } else {
@@ -236,6 +493,7 @@ impl Battle<'_> {
         }
 
         sleep(self.pause_seconds);
+        player_defeated
     }
 
     /// Determines if the target of an attack was hit or not.
@@ -250,26 +508,37 @@ Victory, Defeat, Retreat
 --------------------------------------------------------------------------------
 */
 impl Battle<'_> {
-    /// Go back to the main menu
+    /// Inform the player they fled.
     fn retreat(&mut self) {
         page_header("Battle - Retreat", &Instructions::None);
 
         println!("You have retreated from the battle.");
+        self.player.save();
         pause();
-
-        crate::menus::game_menu::main(self.player);
     }
 
-    /// Receive rewards, then either continue battles if looped or go back to main menu.
-    fn victory(&mut self) {
+    /// Receive rewards for the defeated enemy and return them.
+    fn victory(&mut self) -> Vec<Rewards> {
         page_header(format!("{} - Victory", self.header), &Instructions::None);
 
         println!("You successfully defeated the {}!", self.enemy.name);
-        self.player.health.restore();
+        let max_hp: usize = self.player.max_hp();
+        self.player.health.restore(max_hp);
         self.player.achievements.monsters_killed += 1;
+        self.player.statistics.kill(&self.enemy.name);
         println!();
 
-        let rewards = Rewards::new(XP::get_level(self.player.xp.total()));
+        let mut rewards = Rewards::new(XP::get_level(self.player.xp.total()));
+        Rewards::apply_gold_find(&mut rewards, self.player.loot.gold_find());
+
+        // Drops specific to the enemy, like pack items or the furs of a hunted animal
+        rewards.extend(
+            self.enemy
+                .rewards
+                .iter()
+                .filter(|reward| matches!(reward, Rewards::Item(..)))
+                .cloned(),
+        );
 
         println!("Items Looted:");
 
@@ -277,30 +546,29 @@ impl Battle<'_> {
             println!("- {reward}");
         }
 
-        Rewards::reward_to_player(self.player, rewards);
+        Rewards::reward_to_player(self.player, rewards.clone());
         println!();
 
-        pause();
-        self.player.save();
-
-        if !self.is_looped {
-            crate::menus::game_menu::main(self.player);
+        for quest in Quests::record_kill(self.player, &self.enemy.id) {
+            success(Some(&format!(
+                "Quest complete: {}! You earned {} gold.",
+                quest.name, quest.gold
+            )));
         }
 
-        if self.loops > 0 {
-            self.start();
-        }
+        pause();
+        self.player.announce_level_ups();
+        self.player.save();
 
-        if let Some(end_func) = self.end_function {
-            end_func(self.player);
-        }
+        rewards
     }
 
-    /// Hardmode disabled defeat, basically just revive.
+    /// Player is revived, or in hardmode may lose everything.
     fn defeat(&mut self) {
         page_header(format!("{} - Defeat", self.header), &Instructions::None);
 
         println!("You have been defeated in battle.\n");
+        self.player.statistics.deaths += 1;
         sleep(self.pause_seconds);
 
         println!("You have been rushed to the local physician.\n");
@@ -313,15 +581,16 @@ impl Battle<'_> {
         }
     }
 
-    /// Reset player health and go to main game menu
+    /// Reset player health
     fn revived(&mut self) {
-        println!("You were successfully revived with 100 hp.\n");
+        let max_hp: usize = self.player.max_hp();
+
+        println!("You were successfully revived with {max_hp} hp.\n");
         self.player.health.reset();
+        self.player.health.restore(max_hp);
 
         self.player.save();
         pause();
-
-        crate::menus::game_menu::main(self.player);
     }
 
     /// Player is either revived or has their account reset.
@@ -344,7 +613,6 @@ impl Battle<'_> {
                 pause();
 
                 self.player.reset();
-                crate::menus::game_menu::main(self.player);
             }
             _ => unreachable(),
         }
@@ -1,7 +1,17 @@
 use std::fmt::Display;
 
 use crate::{
-    data::{player::Player, xp::XP},
+    data::{
+        catalog::{Catalog, EnemyDef, RegionDef},
+        inventory::{
+            bank::Account,
+            items,
+            ledger::{Ledger, Source},
+            loot::Gear,
+        },
+        player::Player,
+        xp::{XPType, XP},
+    },
     prelude::random_num,
 };
 
@@ -61,9 +71,33 @@ impl std::fmt::Display for Enemies {
     }
 }
 
+impl Enemies {
+    /// Id used for the species in catalog regions and quests.
+    pub fn id(self) -> String {
+        match self {
+            Enemies::DireWolf => "dire_wolf".to_string(),
+            Enemies::GiantSpider => "giant_spider".to_string(),
+            Enemies::WhiteApe => "white_ape".to_string(),
+            Enemies::DarkElf => "dark_elf".to_string(),
+
+            // One-word names are used as is
+            miscellaneous => format!("{miscellaneous:?}").to_lowercase(),
+        }
+    }
+
+    /// Venomous enemies can poison the player when they hit.
+    pub fn is_venomous(self) -> bool {
+        matches!(self, Enemies::GiantSpider | Enemies::Wyrm)
+    }
+}
+
 #[derive(Clone, Default)]
 pub struct Data {
-    pub flag: Enemies,
+    /// Built-in species, or `None` for enemies added by content packs.
+    pub flag: Option<Enemies>,
+
+    /// Species or catalog id, used to count quest kills.
+    pub id: String,
     pub name: String,
     pub hp: usize,
     pub damage: usize,
@@ -78,28 +112,70 @@ impl Display for Data {
 
 impl Data {
     pub fn new(user_combat_xp: usize, user_hp: usize) -> Self {
-        let user_level: usize = XP::get_level(user_combat_xp);
-        let flag: Enemies = Self::enemy_type();
-        let name: String = flag.to_string();
+        let modded: &Vec<EnemyDef> = &Catalog::get().enemies;
+        let number: usize = random_num(0, Enemies::iter().len() + modded.len() - 1);
 
-        Self {
-            flag,
-            name,
-            hp: Self::hp(user_hp),
-            damage: Self::damage(user_hp),
-            rewards: Rewards::new(user_level),
+        if let Some(flag) = Enemies::iter().get(number) {
+            return Self::species(flag, user_combat_xp, user_hp);
         }
+
+        let Some(definition) = modded.get(number - Enemies::iter().len()) else {
+            crate::panic_menu!("Invalid enemy type chosen.")
+        };
+
+        Self::modded(definition, user_combat_xp, user_hp)
     }
 
-    fn enemy_type() -> Enemies {
-        let max = Enemies::iter().len();
-        let number = random_num(0, max - 1);
-        let option = Enemies::iter().get(number);
+    /// An enemy found in a region, or any enemy if none of its enemies exist anymore.
+    pub fn in_region(region: &RegionDef, user_combat_xp: usize, user_hp: usize) -> Self {
+        let catalog: &Catalog = Catalog::get();
+        let known: Vec<&String> = region.enemies.iter().filter(|id| catalog.is_enemy(id)).collect();
 
-        if let Some(enemy) = option {
-            enemy
-        } else {
-            crate::panic_menu!("Invalid enemy type chosen.")
+        match known.len() {
+            0 => Self::new(user_combat_xp, user_hp),
+            len => Self::from_id(known[random_num(0, len - 1)], user_combat_xp, user_hp)
+                .unwrap_or_else(|| Self::new(user_combat_xp, user_hp)),
+        }
+    }
+
+    /// A built-in species or content pack enemy by id.
+    pub fn from_id(id: &str, user_combat_xp: usize, user_hp: usize) -> Option<Self> {
+        if let Some(flag) = Enemies::iter().find(|species| species.id() == id) {
+            return Some(Self::species(flag, user_combat_xp, user_hp));
+        }
+
+        Catalog::get()
+            .enemy(id)
+            .map(|definition| Self::modded(definition, user_combat_xp, user_hp))
+    }
+
+    /// An enemy added by a content pack, with its drops.
+    fn modded(definition: &EnemyDef, user_combat_xp: usize, user_hp: usize) -> Self {
+        let mut enemy = Self {
+            flag: None,
+            id: definition.id.clone(),
+            name: definition.name.clone(),
+            hp: Self::hp(user_hp) + definition.hp,
+            damage: Self::damage(user_hp) + definition.damage,
+            rewards: Rewards::new(XP::get_level(user_combat_xp)),
+        };
+
+        for (item, max) in &definition.drops {
+            enemy.rewards.push(Rewards::Item(item.clone(), random_num(1, *max)));
+        }
+
+        enemy
+    }
+
+    /// An enemy of a specific built-in species, such as an animal being hunted.
+    pub fn species(flag: Enemies, user_combat_xp: usize, user_hp: usize) -> Self {
+        Self {
+            flag: Some(flag),
+            id: flag.id(),
+            name: flag.to_string(),
+            hp: Self::hp(user_hp),
+            damage: Self::damage(user_hp),
+            rewards: Rewards::new(XP::get_level(user_combat_xp)),
         }
     }
 
@@ -133,6 +209,10 @@ pub enum Rewards {
     Bones(usize),
     DragonHides(usize),
     RunicTablets(usize),
+    Gear(Gear),
+
+    /// Any cataloged item by id, used by content pack drops.
+    Item(String, usize),
 }
 
 impl std::fmt::Display for Rewards {
@@ -149,6 +229,8 @@ impl std::fmt::Display for Rewards {
                 Self::Bones(bones) => format!("Bones: {bones}"),
                 Self::DragonHides(hides) => format!("Dragon Hides: {hides}"),
                 Self::RunicTablets(tablets) => format!("Runic Tablets: {tablets}"),
+                Self::Gear(gear) => format!("Gear: {gear}"),
+                Self::Item(id, quantity) => format!("{}: {quantity}", Catalog::get().item_name(id)),
             }
         )
     }
@@ -196,12 +278,26 @@ impl Rewards {
             gold_reward += random_num(75, 100);
         }
 
+        // One in five enemies drops a piece of gear
+        if random_num(1, 5) == 1 {
+            rewards.push(Rewards::Gear(Gear::generate(player_level)));
+        }
+
         rewards.push(Rewards::XP(xp_reward));
         rewards.push(Rewards::Gold(gold_reward));
 
         rewards
     }
 
+    /// Increase gold rewards by a percentage, used by gold find affixes.
+    pub fn apply_gold_find(rewards: &mut [Self], percent: usize) {
+        for reward in rewards {
+            if let Rewards::Gold(gold) = reward {
+                *gold += *gold * percent / 100;
+            }
+        }
+    }
+
     pub fn xp(player_level: usize) -> usize {
         let mut xp_reward: usize = random_num(0, 10);
 
@@ -229,14 +325,22 @@ impl Rewards {
     pub fn reward_to_player(player: &mut Player, rewards: Vec<Self>) {
         for reward in rewards {
             match reward {
-                Rewards::Potions(quantity) => player.items.potions += quantity,
-                Rewards::Bones(quantity) => player.items.bones += quantity,
-                Rewards::Rubies(quantity) => player.items.rubies += quantity,
-                Rewards::DragonHides(quantity) => player.items.dragon_hides += quantity,
-                Rewards::MagicScrolls(quantity) => player.items.magic_scrolls += quantity,
-                Rewards::RunicTablets(quantity) => player.items.runic_tablets += quantity,
-                Rewards::Gold(gold) => player.bank.wallet += gold,
-                Rewards::XP(xp) => player.xp.combat += xp,
+                Rewards::Potions(quantity) => *player.items.get(items::Types::Potions) += quantity,
+                Rewards::Bones(quantity) => *player.items.get(items::Types::Bones) += quantity,
+                Rewards::Rubies(quantity) => *player.items.get(items::Types::Rubies) += quantity,
+                Rewards::DragonHides(quantity) => *player.items.get(items::Types::DragonHides) += quantity,
+                Rewards::MagicScrolls(quantity) => *player.items.get(items::Types::MagicScrolls) += quantity,
+                Rewards::RunicTablets(quantity) => *player.items.get(items::Types::RunicTablets) += quantity,
+                Rewards::Gold(gold) => Ledger::credit(player, Source::Battle, Account::Wallet, gold),
+                Rewards::XP(xp) => {
+                    if let Err(message) = XP::add(player, XPType::Combat, xp) {
+                        message.print(true);
+                    }
+                }
+                Rewards::Gear(gear) => {
+                    player.loot.add(gear);
+                }
+                Rewards::Item(id, quantity) => *player.items.get(id) += quantity,
             }
         }
     }
@@ -1,79 +1,111 @@
 use crate::{
-    data::{inventory::equipment::Equipment, player::Player},
-    prelude::{page_header, pause, random_num, select, unreachable, Instructions},
+    data::{
+        consumables::{Consumable, Effects},
+        health::{Food, Health},
+        inventory::equipment::Equipment,
+        player::Player,
+    },
+    prelude::{csv_table, page_header, pause, select, unreachable, Instructions},
 };
+use strum::IntoEnumIterator;
 
-/// Choose between equipment and healing
+/// Choose between equipment, potions, and food
 pub fn battle_menu(player: &mut Player) {
     page_header("Battle Inventory", &Instructions::Keyboard);
 
-    let choice: usize = select(&["1. Equipment", "2. Healing", "NAV: Go Back"], None);
+    let choice: usize = select(&["1. Equipment", "2. Potions", "3. Food", "NAV: Go Back"], None);
 
     match choice {
         0 => {
             Equipment::menu(player);
             battle_menu(player);
         }
-        1 => healing_menu(player),
-        2 => {} // just returns to battle menu since the battle menu function is recursive called after this menu
+        1 => {
+            potion_menu(player);
+            battle_menu(player);
+        }
+        2 => {
+            food_menu(player);
+            battle_menu(player);
+        }
+        3 => {} // just returns to battle menu since the battle menu function is recursive called after this menu
         _ => unreachable(),
     }
 }
 
-pub fn healing_menu(player: &mut Player) {
-    page_header("Healing Inventory", &Instructions::Keyboard);
+/// Choose a potion to drink until the player goes back.
+pub fn potion_menu(player: &mut Player) {
+    let consumables: Vec<Consumable> = Consumable::iter().collect();
+    let mut choices: Vec<String> = consumables
+        .iter()
+        .map(|consumable| format!("Drink {consumable}"))
+        .collect();
+    choices.push("NAV: Go Back".to_string());
 
-    println!("Potions: {}", player.items.potions);
-    println!("Food: {}", player.items.food);
-    println!();
+    loop {
+        page_header("Potions", &Instructions::Keyboard);
 
-    let choice: usize = select(&["1. Use Potion", "2. Eat Food", "NAV: Go Back"], None);
+        println!("HP: {}/{}", player.health.hp, player.max_hp());
 
-    match choice {
-        0 => {
-            use_potion(player);
-            healing_menu(player);
+        if player.health.poison > 0 {
+            println!("Poisoned: {} rounds left", player.health.poison);
         }
-        1 => {
-            eat_food(player);
-            healing_menu(player);
+
+        println!();
+        Effects::table(player);
+
+        let Some(consumable) = consumables.get(select(&choices, None)).copied() else {
+            return;
+        };
+
+        match Effects::consume(player, consumable) {
+            Ok(message) => {
+                println!("{message}");
+                pause();
+            }
+            Err(message) => message.print(true),
         }
-        2 => battle_menu(player),
-        _ => unreachable(),
     }
 }
 
-pub fn use_potion(player: &mut Player) {
-    if player.items.potions == 0 {
-        println!("You do not have enough potions.");
-        pause();
-        return;
-    }
+/// Choose a food to eat until the player goes back.
+pub fn food_menu(player: &mut Player) {
+    let foods: Vec<Food> = Food::iter().collect();
+    let mut choices: Vec<String> = foods.iter().map(|food| format!("Eat {food}")).collect();
+    choices.push("NAV: Go Back".to_string());
 
-    player.items.potions -= 1;
+    loop {
+        page_header("Food", &Instructions::Keyboard);
 
-    let health = random_num(1, 5);
-    player.health.hp += health;
+        println!("Hunger: {} ({})\n", player.health.hunger, player.health.status());
 
-    println!("Your health increased {} hp, and is now {}.", health, player.health.hp);
-    pause();
-}
+        let mut strings: Vec<String> = vec!["Food,Quality,Satisfies,Quantity".to_string()];
 
-pub fn eat_food(player: &mut Player) {
-    if player.items.food == 0 {
-        println!("You do not have enough food.");
-        pause();
-        return;
-    }
+        for food in &foods {
+            let (min, max) = food.restores();
 
-    player.items.food -= 1;
+            strings.push(format!(
+                "{food},{},{min}-{max} Hunger,{}",
+                food.quality(),
+                player.items.quantity(food.item())
+            ));
+        }
+
+        csv_table(&strings);
 
-    let hunger = random_num(1, 5);
-    player.health.hunger -= hunger;
+        let Some(food) = foods.get(select(&choices, None)).copied() else {
+            return;
+        };
 
-    println!(
-        "Your hunger decreased {} points, and is now {}.",
-        hunger, player.health.hunger
-    );
-    pause();
+        match Health::eat(player, food) {
+            Ok(satisfied) => {
+                println!(
+                    "Your hunger decreased {} points, and is now {}.",
+                    satisfied, player.health.hunger
+                );
+                pause();
+            }
+            Err(message) => message.print(true),
+        }
+    }
 }
@@ -1,7 +1,181 @@
+/*!
+# Achievements
+
+Every achievement is listed in a registry with a condition measured as
+progress toward a goal. Achievements are checked whenever something
+happens in the game (battles, guild work, level-ups, and returning to the
+main menu). Unlocking one shows a notification, records the in-game time
+it was unlocked, and grants its reward.
+
+Hidden achievements keep their name and description secret until they
+are unlocked.
+
+Adding an achievement only needs a new entry in [`REGISTRY`].
+*/
 use serde::{Deserialize, Serialize};
+use std::{collections::BTreeMap, fmt::Display};
+use strum::IntoEnumIterator;
+
+use crate::data::{
+    clock::Clock,
+    guilds::{Guild, Rank},
+    inventory::{
+        bank::Account,
+        items,
+        ledger::{Ledger, Source},
+    },
+    player::Player,
+    xp::XP,
+};
+use crate::prelude::{checkmark, csv_table, progress_bar, success};
+
+/// Width of the progress bars in the Hall of Records.
+const BAR_WIDTH: usize = 10;
+
+/// What unlocking an achievement grants.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Reward {
+    Gold(usize),
+    StatPoints(usize),
+    Item(items::Types, usize),
+}
+
+impl Display for Reward {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Reward::Gold(gold) => write!(f, "{gold} Gold"),
+            Reward::StatPoints(points) => write!(f, "{points} Stat Points"),
+            Reward::Item(item, quantity) => write!(f, "{quantity} {item}"),
+        }
+    }
+}
+
+pub struct Achievement {
+    pub id: &'static str,
+    pub name: &'static str,
+    pub description: &'static str,
+    pub hidden: bool,
+    pub reward: Option<Reward>,
 
-use crate::data::{player::Player, xp::XP};
-use crate::prelude::{checkmark, csv_table};
+    /// Current progress and the goal that unlocks the achievement.
+    pub progress: fn(&Player) -> (usize, usize),
+}
+
+impl Achievement {
+    pub fn is_met(&self, player: &Player) -> bool {
+        let (current, goal) = (self.progress)(player);
+
+        current >= goal
+    }
+}
+
+/// Every achievement in the game.
+pub const REGISTRY: &[Achievement] = &[
+    Achievement {
+        id: "first_blood",
+        name: "First Blood",
+        description: "Defeat your first monster",
+        hidden: false,
+        reward: Some(Reward::Gold(50)),
+        progress: |player| (player.achievements.monsters_killed, 1),
+    },
+    Achievement {
+        id: "monster_hunter",
+        name: "Monster Hunter",
+        description: "Defeat 100 monsters",
+        hidden: false,
+        reward: Some(Reward::Gold(1_000)),
+        progress: |player| (player.achievements.monsters_killed, 100),
+    },
+    Achievement {
+        id: "slayer",
+        name: "Slayer",
+        description: "Defeat 1,000 monsters",
+        hidden: false,
+        reward: Some(Reward::StatPoints(5)),
+        progress: |player| (player.achievements.monsters_killed, 1_000),
+    },
+    Achievement {
+        id: "veteran",
+        name: "Veteran",
+        description: "Reach combat level 25",
+        hidden: false,
+        reward: Some(Reward::StatPoints(3)),
+        progress: |player| (XP::get_level(player.xp.combat), 25),
+    },
+    Achievement {
+        id: "level_100",
+        name: "Centurion",
+        description: "Reach a total level of 100",
+        hidden: false,
+        reward: Some(Reward::StatPoints(10)),
+        progress: |player| (XP::get_level(player.xp.total()), 100),
+    },
+    Achievement {
+        id: "stronghold_defeated",
+        name: "Conqueror",
+        description: "Defeat the Stronghold",
+        hidden: false,
+        reward: Some(Reward::Gold(10_000)),
+        progress: |player| (usize::from(player.achievements.stronghold_defeated), 1),
+    },
+    Achievement {
+        id: "journeyman",
+        name: "Journeyman",
+        description: "Join a guild",
+        hidden: false,
+        reward: Some(Reward::Gold(100)),
+        progress: |player| (player.guilds.memberships.len().min(1), 1),
+    },
+    Achievement {
+        id: "jack_of_all_trades",
+        name: "Jack of All Trades",
+        description: "Be a member of every guild at once",
+        hidden: false,
+        reward: Some(Reward::Gold(2_500)),
+        progress: |player| (player.guilds.memberships.len(), Guild::iter().count()),
+    },
+    Achievement {
+        id: "grandmaster",
+        name: "Grandmaster",
+        description: "Reach the Grandmaster rank in a guild",
+        hidden: false,
+        reward: Some(Reward::Item(items::Types::RunicTablets, 1)),
+        progress: |player| {
+            let reached: bool = player
+                .guilds
+                .memberships
+                .values()
+                .any(|membership| membership.rank == Rank::Grandmaster);
+
+            (usize::from(reached), 1)
+        },
+    },
+    Achievement {
+        id: "earned_million_gold",
+        name: "Millionaire",
+        description: "Have a net worth of one million gold",
+        hidden: false,
+        reward: Some(Reward::StatPoints(5)),
+        progress: |player| (player.bank.net_worth(), 1_000_000),
+    },
+    Achievement {
+        id: "most_wanted",
+        name: "Most Wanted",
+        description: "Run up a bounty of 1,000 gold",
+        hidden: true,
+        reward: Some(Reward::Item(items::Types::MagicScrolls, 2)),
+        progress: |player| (player.bounty, 1_000),
+    },
+    Achievement {
+        id: "hacked_the_game",
+        name: "Hacker",
+        description: "Enable developer mode",
+        hidden: true,
+        reward: None,
+        progress: |player| (usize::from(player.achievements.hacked_the_game), 1),
+    },
+];
 
 #[derive(Serialize, Deserialize, Debug, Clone, Default)]
 pub struct Achievements {
@@ -9,38 +183,162 @@ pub struct Achievements {
     pub monsters_killed: usize,
     pub stronghold_defeated: bool,
 
-    // Determined when achievements viewed
-    pub earned_million_gold: bool,
-    pub level_100_reached: bool,
+    // Determined in the settings menu
     pub hacked_the_game: bool,
+
+    /// In-game hour each achievement was unlocked, keyed by id.
+    #[serde(default)]
+    pub unlocked: BTreeMap<String, usize>,
 }
 
 impl Achievements {
+    /// Start the counters over, keeping unlocked achievements so their rewards aren't paid twice.
     pub fn reset(&mut self) {
-        *self = Self::default();
+        *self = Self {
+            unlocked: std::mem::take(&mut self.unlocked),
+            ..Self::default()
+        };
     }
 
-    pub fn table(&self) {
-        csv_table(&[
-            "Achievement,Data".to_string(),
-            format!("Monsters Killed,{}", self.monsters_killed),
-            format!("Stronghold Defeated?,{}", checkmark(self.stronghold_defeated)),
-            format!("Earned One Million Gold?,{}", checkmark(self.earned_million_gold)),
-            format!("Passed level 100?,{}", checkmark(self.level_100_reached)),
-            format!("Hacked the Game?,{}", checkmark(self.hacked_the_game)),
-        ]);
+    pub fn is_unlocked(&self, id: &str) -> bool {
+        self.unlocked.contains_key(id)
     }
 
-    /// Detects earned achievements, namely 1Mil gold and level 100
-    pub fn check(player: &mut Player) {
-        if player.bank.net_worth() >= 1_000_000 {
-            player.achievements.earned_million_gold = true;
+    pub fn table(player: &Player) {
+        let mut strings: Vec<String> = vec!["Achievement,Description,Progress,Reward,Unlocked".to_string()];
+
+        for achievement in REGISTRY {
+            let unlocked: Option<&usize> = player.achievements.unlocked.get(achievement.id);
+
+            if achievement.hidden && unlocked.is_none() {
+                strings.push("???,Hidden achievement,-,???,".to_string());
+                continue;
+            }
+
+            let (current, goal) = (achievement.progress)(player);
+
+            let progress: String = if unlocked.is_some() {
+                progress_bar(goal, goal, BAR_WIDTH)
+            } else {
+                progress_bar(current, goal, BAR_WIDTH)
+            };
+
+            let reward: String = achievement
+                .reward
+                .map_or_else(|| "-".to_string(), |reward| reward.to_string());
+
+            let when: String = unlocked.map_or_else(String::new, |hours| {
+                // The clock's comma would split the table cell
+                format!("{} {}", checkmark(true), Clock { hours: *hours }).replace(',', "")
+            });
+
+            strings.push(format!(
+                "{},{},{progress},{reward},{when}",
+                achievement.name, achievement.description
+            ));
+        }
+
+        csv_table(&strings);
+
+        println!("Unlocked: {}/{}\n", player.achievements.unlocked.len(), REGISTRY.len());
+    }
+
+    /// Unlock every achievement whose condition is now met, returning the newly unlocked ones.
+    pub fn unlock_new(player: &mut Player) -> Vec<&'static Achievement> {
+        let mut unlocked: Vec<&'static Achievement> = vec![];
+
+        for achievement in REGISTRY {
+            if player.achievements.is_unlocked(achievement.id) || !achievement.is_met(player) {
+                continue;
+            }
+
+            player
+                .achievements
+                .unlocked
+                .insert(achievement.id.to_string(), player.clock.hours);
+
+            match achievement.reward {
+                Some(Reward::Gold(gold)) => Ledger::credit(player, Source::Achievements, Account::Wallet, gold),
+                Some(Reward::StatPoints(points)) => player.xp.stat_points += points,
+                Some(Reward::Item(item, quantity)) => *player.items.get(item) += quantity,
+                None => {}
+            }
+
+            unlocked.push(achievement);
         }
 
-        if XP::get_level(player.xp.total()) >= 100 {
-            player.achievements.level_100_reached = true;
+        unlocked
+    }
+
+    /// Checks for newly earned achievements after a game event, showing a
+    /// notification for each one and saving the profile if any were unlocked.
+    pub fn check(player: &mut Player) {
+        let unlocked: Vec<&'static Achievement> = Self::unlock_new(player);
+
+        if unlocked.is_empty() {
+            return;
         }
 
         player.save();
+
+        for achievement in unlocked {
+            let reward: String = achievement
+                .reward
+                .map_or_else(String::new, |reward| format!(" Reward: {reward}."));
+
+            success(Some(&format!(
+                "Achievement unlocked: {}! {}.{reward}",
+                achievement.name, achievement.description
+            )));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn unlocks_once_with_reward() {
+        use super::Achievements;
+        use crate::data::player::Player;
+
+        let mut player = Player::default();
+        let wallet: usize = player.bank.wallet;
+
+        assert!(Achievements::unlock_new(&mut player).is_empty());
+
+        player.achievements.monsters_killed = 1;
+
+        let unlocked = Achievements::unlock_new(&mut player);
+
+        assert_eq!(unlocked.len(), 1);
+        assert_eq!(unlocked[0].id, "first_blood");
+        assert_eq!(player.bank.wallet, wallet + 50);
+        assert!(Achievements::unlock_new(&mut player).is_empty());
+    }
+
+    #[test]
+    fn unlocks_survive_death() {
+        use super::Achievements;
+        use crate::data::player::Player;
+
+        let mut player = Player::default();
+
+        player.achievements.monsters_killed = 1;
+        assert_eq!(Achievements::unlock_new(&mut player).len(), 1);
+
+        player.die();
+        player.achievements.monsters_killed = 1;
+
+        assert!(player.achievements.is_unlocked("first_blood"));
+        assert!(Achievements::unlock_new(&mut player).is_empty());
+    }
+
+    #[test]
+    fn registry_ids_are_unique() {
+        use super::REGISTRY;
+
+        for (index, achievement) in REGISTRY.iter().enumerate() {
+            assert!(REGISTRY[index + 1..].iter().all(|other| other.id != achievement.id));
+        }
     }
 }
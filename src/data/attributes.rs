@@ -0,0 +1,126 @@
+/*!
+# Attributes
+
+Core character attributes, raised by spending the stat points earned from
+combat level-ups.
+
+- Strength: More damage and a higher carry capacity
+- Defense: Less damage taken
+- Agility: A better chance to hit
+- Vitality: More max hp
+- Intelligence: Better odds when upgrading equipment at the smithy
+*/
+use crate::{
+    data::player::Player,
+    prelude::{csv_table, error},
+};
+use serde::{Deserialize, Serialize};
+use std::fmt::Display;
+use strum::IntoEnumIterator;
+use strum_macros::EnumIter;
+
+/// Max hp before vitality.
+pub const BASE_HP: usize = 100;
+
+/// Percent chance to hit before agility.
+pub const BASE_HIT_CHANCE: usize = 50;
+
+/// Highest percent chance to hit.
+pub const MAX_HIT_CHANCE: usize = 90;
+
+#[derive(Clone, Copy, Debug, PartialEq, EnumIter)]
+pub enum Attribute {
+    Strength,
+    Defense,
+    Agility,
+    Vitality,
+    Intelligence,
+}
+
+impl Display for Attribute {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{self:?}")
+    }
+}
+
+impl Attribute {
+    /// What one point in the attribute does.
+    pub fn effect(self) -> &'static str {
+        match self {
+            Attribute::Strength => "+2 damage, +20 carry capacity",
+            Attribute::Defense => "+2 defense",
+            Attribute::Agility => "+2% hit chance",
+            Attribute::Vitality => "+10 max hp",
+            Attribute::Intelligence => "+1% upgrade success chance",
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
+pub struct Attributes {
+    pub strength: usize,
+    pub defense: usize,
+    pub agility: usize,
+    pub vitality: usize,
+    pub intelligence: usize,
+}
+
+impl Attributes {
+    pub fn reset(&mut self) {
+        *self = Self::default();
+    }
+
+    pub fn get(&mut self, attribute: Attribute) -> &mut usize {
+        match attribute {
+            Attribute::Strength => &mut self.strength,
+            Attribute::Defense => &mut self.defense,
+            Attribute::Agility => &mut self.agility,
+            Attribute::Vitality => &mut self.vitality,
+            Attribute::Intelligence => &mut self.intelligence,
+        }
+    }
+
+    pub fn max_hp(&self) -> usize {
+        BASE_HP + self.vitality * 10
+    }
+
+    pub fn bonus_damage(&self) -> usize {
+        self.strength * 2
+    }
+
+    pub fn bonus_defense(&self) -> usize {
+        self.defense * 2
+    }
+
+    pub fn bonus_capacity(&self) -> usize {
+        self.strength * 20
+    }
+
+    pub fn hit_chance(&self) -> usize {
+        (BASE_HIT_CHANCE + self.agility * 2).min(MAX_HIT_CHANCE)
+    }
+
+    pub fn table(&mut self) {
+        let mut strings: Vec<String> = vec!["Attribute,Points,Each Point Gives".to_string()];
+
+        for attribute in Attribute::iter() {
+            strings.push(format!("{attribute},{},{}", self.get(attribute), attribute.effect()));
+        }
+
+        csv_table(&strings);
+    }
+
+    /// Spend unspent stat points on an attribute.
+    pub fn allocate(player: &mut Player, attribute: Attribute, points: usize) -> error::Result<()> {
+        if points == 0 || player.xp.stat_points < points {
+            return Err(Box::new(error::Miscellaneous::Custom(
+                "You do not have enough stat points.",
+            )));
+        }
+
+        player.xp.stat_points -= points;
+        *player.attributes.get(attribute) += points;
+
+        Ok(())
+    }
+}
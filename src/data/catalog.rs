@@ -0,0 +1,473 @@
+/*!
+# Item Catalog
+
+Describes every item, weapon, armor piece, tool, and guild membership sold
+in the game, along with their prices and stats, the crafting recipes, the
+regions of the realm and their quests, plus any enemies added by content
+packs. The catalog is loaded once at startup and shared by the whole game.
+
+The built-in catalog is compiled into the game. Players can override it by
+placing a `catalog.toml` file in the game folder, so new content needs no
+code change.
+
+# Example
+
+```
+use albion_terminal_rpg::data::catalog::Catalog;
+
+let catalog = Catalog::get();
+
+if let Some(bait) = catalog.item("bait") {
+    println!("{} costs {} gold.", bait.name, bait.price);
+}
+```
+*/
+use crate::{
+    combat::enemy::Enemies,
+    data::{
+        guilds::Guild,
+        inventory::equipment::Slot,
+        inventory::items,
+        inventory::tools::Tool,
+        mods::ContentPack,
+        xp::{LevelCurve, XPType, MAX_CAP, MAX_GROWTH},
+    },
+    panic_menu,
+    prelude::{error, game_directory, mods_directory, warning},
+};
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    path::Path,
+    sync::OnceLock,
+};
+use strum::IntoEnumIterator;
+
+/// The catalog shipped with the game.
+const BUILTIN: &str = include_str!("catalog.toml");
+
+/// File name of the player-provided catalog in the game folder.
+pub const FILE_NAME: &str = "catalog.toml";
+
+static CATALOG: OnceLock<Catalog> = OnceLock::new();
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct ItemDef {
+    pub id: String,
+    pub name: String,
+    pub price: usize,
+
+    /// Weight of a single item. Items without a weight are carried for free.
+    #[serde(default)]
+    pub weight: usize,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct WeaponDef {
+    pub id: String,
+    pub name: String,
+    pub price: usize,
+    pub damage: usize,
+    pub durability: usize,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct ArmorDef {
+    pub id: String,
+    pub name: String,
+    pub slot: Slot,
+    pub price: usize,
+    pub defense: usize,
+    #[serde(default)]
+    pub damage: usize,
+    pub durability: usize,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+pub enum OutputKind {
+    Item,
+    Weapon,
+    Armor,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct CraftOutput {
+    pub kind: OutputKind,
+    pub id: String,
+    pub quantity: usize,
+}
+
+/// A crafting recipe. Inputs map item ids to the quantity consumed, and
+/// crafting requires a level in the given skill.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct RecipeDef {
+    pub id: String,
+    pub name: String,
+    pub skill: XPType,
+    pub level: usize,
+    pub xp: usize,
+    pub inputs: BTreeMap<String, usize>,
+    pub output: CraftOutput,
+}
+
+/// An enemy added by a content pack. Health and damage are added on top
+/// of the usual scaling, and drops map item ids to the most that can drop.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct EnemyDef {
+    pub id: String,
+    pub name: String,
+    #[serde(default)]
+    pub hp: usize,
+    #[serde(default)]
+    pub damage: usize,
+    #[serde(default)]
+    pub drops: BTreeMap<String, usize>,
+}
+
+/// Price of a tool and the shifts of work it lasts before breaking.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct ToolDef {
+    pub price: usize,
+    pub durability: usize,
+}
+
+/// A region of the realm the player can explore. Enemies are built-in
+/// species ids like `dire_wolf` or enemy ids from content packs, and the
+/// level is the combat level needed to enter.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct RegionDef {
+    pub id: String,
+    pub name: String,
+    #[serde(default)]
+    pub description: String,
+    #[serde(default)]
+    pub level: usize,
+    pub enemies: Vec<String>,
+}
+
+/// A quest to defeat a number of one enemy, paying gold, combat xp, and
+/// items (by item id) once finished.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct QuestDef {
+    pub id: String,
+    pub name: String,
+    pub enemy: String,
+    pub count: usize,
+    #[serde(default)]
+    pub gold: usize,
+    #[serde(default)]
+    pub xp: usize,
+    #[serde(default)]
+    pub items: BTreeMap<String, usize>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct Catalog {
+    pub items: Vec<ItemDef>,
+    pub weapons: Vec<WeaponDef>,
+    pub armor: Vec<ArmorDef>,
+    #[serde(default)]
+    pub enemies: Vec<EnemyDef>,
+    #[serde(default)]
+    pub recipes: Vec<RecipeDef>,
+    #[serde(default)]
+    pub regions: Vec<RegionDef>,
+    #[serde(default)]
+    pub quests: Vec<QuestDef>,
+    #[serde(default)]
+    pub levels: LevelCurve,
+    pub guilds: BTreeMap<Guild, usize>,
+    #[serde(default)]
+    pub tools: BTreeMap<Tool, ToolDef>,
+
+    /// Content packs merged into the catalog, in load order.
+    #[serde(skip)]
+    pub packs: Vec<String>,
+}
+
+impl TryFrom<&str> for Catalog {
+    type Error = error::Catalog;
+
+    fn try_from(data: &str) -> Result<Self, Self::Error> {
+        let catalog: Catalog = toml::from_str(data).map_err(|error| error::Catalog::Parse(error.to_string()))?;
+
+        catalog.validate()?;
+        Ok(catalog)
+    }
+}
+
+impl Catalog {
+    /**
+    Load the catalog from the game folder, falling back to the built-in
+    catalog, then merge in any content packs. Should be called once at
+    startup; an invalid catalog file stops the game with a description of
+    the problems, and pack conflicts are shown as a warning. If the catalog
+    was already in use, it is kept and a warning is shown instead.
+    */
+    pub fn load() {
+        let path = Path::new(&game_directory()).join(FILE_NAME);
+
+        let catalog = match std::fs::read_to_string(&path) {
+            Ok(contents) => Self::try_from(contents.as_str()),
+            Err(_) => Self::builtin(),
+        };
+
+        let mut catalog = match catalog {
+            Ok(catalog) => catalog,
+            Err(error) => panic_menu!("{}: {}", path.display(), error),
+        };
+
+        let (packs, mut problems) = ContentPack::discover();
+        problems.extend(catalog.merge(packs));
+
+        if let Err(error) = catalog.validate() {
+            panic_menu!("{}: {}", mods_directory(), error);
+        }
+
+        if !problems.is_empty() {
+            warning(Some(&format!(
+                "Some content packs could not be fully loaded:\n- {}",
+                problems.join("\n- ")
+            )));
+        }
+
+        if CATALOG.set(catalog).is_err() {
+            warning(Some(
+                "The catalog was already in use before it was loaded, so the game folder catalog and content packs were ignored.",
+            ));
+        }
+    }
+
+    /// The catalog in use, loading the built-in catalog if none was loaded.
+    pub fn get() -> &'static Catalog {
+        CATALOG.get_or_init(|| match Self::builtin() {
+            Ok(catalog) => catalog,
+            Err(error) => panic_menu!("Built-in catalog: {}", error),
+        })
+    }
+
+    pub fn builtin() -> Result<Self, error::Catalog> {
+        Self::try_from(BUILTIN)
+    }
+
+    pub fn item(&self, id: &str) -> Option<&ItemDef> {
+        self.items.iter().find(|item| item.id == id)
+    }
+
+    pub fn weapon(&self, id: &str) -> Option<&WeaponDef> {
+        self.weapons.iter().find(|weapon| weapon.id == id)
+    }
+
+    pub fn armor(&self, id: &str) -> Option<&ArmorDef> {
+        self.armor.iter().find(|armor| armor.id == id)
+    }
+
+    pub fn enemy(&self, id: &str) -> Option<&EnemyDef> {
+        self.enemies.iter().find(|enemy| enemy.id == id)
+    }
+
+    pub fn recipe(&self, id: &str) -> Option<&RecipeDef> {
+        self.recipes.iter().find(|recipe| recipe.id == id)
+    }
+
+    pub fn region(&self, id: &str) -> Option<&RegionDef> {
+        self.regions.iter().find(|region| region.id == id)
+    }
+
+    pub fn quest(&self, id: &str) -> Option<&QuestDef> {
+        self.quests.iter().find(|quest| quest.id == id)
+    }
+
+    /// Whether an id names a built-in enemy species or an enemy from a content pack.
+    pub fn is_enemy(&self, id: &str) -> bool {
+        Enemies::iter().any(|species| species.id() == id) || self.enemy(id).is_some()
+    }
+
+    /// Display name for an item id, or the id itself if it isn't cataloged.
+    pub fn item_name(&self, id: &str) -> String {
+        self.item(id).map_or_else(|| id.to_string(), |item| item.name.clone())
+    }
+
+    /// Checks ids are unique, stats are usable, and every core item and guild exists.
+    pub fn validate(&self) -> Result<(), error::Catalog> {
+        fn duplicates<'a>(section: &str, ids: impl Iterator<Item = &'a String>, problems: &mut Vec<String>) {
+            let mut seen: BTreeSet<&String> = BTreeSet::new();
+
+            for id in ids {
+                if !seen.insert(id) {
+                    problems.push(format!("Duplicate {section} id '{id}'."));
+                }
+            }
+        }
+
+        let mut problems: Vec<String> = vec![];
+
+        duplicates("item", self.items.iter().map(|item| &item.id), &mut problems);
+        duplicates("weapon", self.weapons.iter().map(|weapon| &weapon.id), &mut problems);
+        duplicates("armor", self.armor.iter().map(|armor| &armor.id), &mut problems);
+        duplicates("enemy", self.enemies.iter().map(|enemy| &enemy.id), &mut problems);
+        duplicates("recipe", self.recipes.iter().map(|recipe| &recipe.id), &mut problems);
+        duplicates("region", self.regions.iter().map(|region| &region.id), &mut problems);
+        duplicates("quest", self.quests.iter().map(|quest| &quest.id), &mut problems);
+
+        for item in &self.items {
+            if item.price == 0 {
+                problems.push(format!("Item '{}' must have a price.", item.id));
+            }
+        }
+
+        for weapon in &self.weapons {
+            if weapon.price == 0 || weapon.durability == 0 {
+                problems.push(format!("Weapon '{}' must have a price and durability.", weapon.id));
+            }
+        }
+
+        for armor in &self.armor {
+            if armor.price == 0 || armor.durability == 0 {
+                problems.push(format!("Armor '{}' must have a price and durability.", armor.id));
+            }
+        }
+
+        for enemy in &self.enemies {
+            for (item, max) in &enemy.drops {
+                if self.item(item).is_none() || *max == 0 {
+                    problems.push(format!(
+                        "Enemy '{}' drops unknown item '{item}' or none of it.",
+                        enemy.id
+                    ));
+                }
+            }
+        }
+
+        for recipe in &self.recipes {
+            let output_exists: bool = match recipe.output.kind {
+                OutputKind::Item => self.item(&recipe.output.id).is_some(),
+                OutputKind::Weapon => self.weapon(&recipe.output.id).is_some(),
+                OutputKind::Armor => self.armor(&recipe.output.id).is_some(),
+            };
+
+            if !output_exists || recipe.output.quantity == 0 {
+                problems.push(format!("Recipe '{}' makes an unknown item or none of it.", recipe.id));
+            }
+
+            for (item, quantity) in &recipe.inputs {
+                if self.item(item).is_none() || *quantity == 0 {
+                    problems.push(format!(
+                        "Recipe '{}' needs unknown item '{item}' or none of it.",
+                        recipe.id
+                    ));
+                }
+            }
+        }
+
+        self.validate_regions(&mut problems);
+
+        if self.levels.base == 0 || self.levels.cap == 0 {
+            problems.push("The level curve needs a base and cap above 0.".to_string());
+        }
+
+        if self.levels.growth > MAX_GROWTH || self.levels.cap > MAX_CAP {
+            problems.push(format!(
+                "The level curve growth can be at most {MAX_GROWTH}% and the cap at most {MAX_CAP}."
+            ));
+        }
+
+        for core in items::Types::iter() {
+            if self.item(core.id()).is_none() {
+                problems.push(format!("Missing core item '{}'.", core.id()));
+            }
+        }
+
+        for guild in Guild::iter() {
+            if !self.guilds.contains_key(&guild) {
+                problems.push(format!("Missing price for the {guild} guild."));
+            }
+        }
+
+        for tool in Tool::iter() {
+            match self.tools.get(&tool) {
+                Some(definition) if definition.price > 0 && definition.durability > 0 => {}
+                Some(_) => problems.push(format!("The {tool} must have a price and durability.")),
+                None => problems.push(format!("Missing price for the {tool}.")),
+            }
+        }
+
+        if problems.is_empty() {
+            Ok(())
+        } else {
+            Err(error::Catalog::Invalid(problems))
+        }
+    }
+
+    /// Checks every region and quest names known enemies and rewards.
+    fn validate_regions(&self, problems: &mut Vec<String>) {
+        for region in &self.regions {
+            if region.enemies.is_empty() {
+                problems.push(format!("Region '{}' must have enemies.", region.id));
+            }
+
+            for enemy in &region.enemies {
+                if !self.is_enemy(enemy) {
+                    problems.push(format!("Region '{}' has unknown enemy '{enemy}'.", region.id));
+                }
+            }
+        }
+
+        for quest in &self.quests {
+            if !self.is_enemy(&quest.enemy) || quest.count == 0 {
+                problems.push(format!(
+                    "Quest '{}' asks for unknown enemy '{}' or none of it.",
+                    quest.id, quest.enemy
+                ));
+            }
+
+            for (item, quantity) in &quest.items {
+                if self.item(item).is_none() || *quantity == 0 {
+                    problems.push(format!(
+                        "Quest '{}' rewards unknown item '{item}' or none of it.",
+                        quest.id
+                    ));
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn builtin_catalog_is_valid() {
+        if let Err(error) = super::Catalog::builtin() {
+            panic!("{error}");
+        }
+    }
+
+    #[test]
+    fn regions_and_quests_need_known_enemies() {
+        use super::{Catalog, QuestDef, RegionDef};
+        use std::collections::BTreeMap;
+
+        let mut catalog = Catalog::builtin().expect("Built-in catalog should be valid.");
+
+        catalog.regions.push(RegionDef {
+            id: "swamp".to_string(),
+            name: "Swamp".to_string(),
+            description: String::new(),
+            level: 0,
+            enemies: vec!["goblin".to_string(), "bog_witch".to_string()],
+        });
+
+        catalog.quests.push(QuestDef {
+            id: "witch_hunt".to_string(),
+            name: "Witch Hunt".to_string(),
+            enemy: "bog_witch".to_string(),
+            count: 1,
+            gold: 0,
+            xp: 0,
+            items: BTreeMap::new(),
+        });
+
+        assert!(catalog.is_enemy("goblin") && catalog.is_enemy("dire_wolf"));
+        assert!(matches!(catalog.validate(), Err(super::error::Catalog::Invalid(problems)) if problems.len() == 2));
+    }
+}
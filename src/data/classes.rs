@@ -0,0 +1,298 @@
+/*!
+# Classes
+
+Every player picks a class when registering. Each class has a skill tree
+whose skills unlock as the player's combat level rises. Active skills are
+abilities used in battle, once per fight. Passive skills are always on and
+improve the player in battle or at guild work.
+
+# Skill Trees
+
+- Warrior: Power Strike, Iron Skin, Second Wind, Forgemaster
+- Ranger: Aimed Shot, Outdoorsman, Keen Eye, Volley
+- Mage: Fireball, Alchemist, Mana Shield, Meteor
+- Rogue: Backstab, Light Fingers, Evasion, Assassin
+*/
+use crate::{
+    data::{
+        attributes::MAX_HIT_CHANCE,
+        player::Player,
+        xp::{XPType, XP},
+    },
+    prelude::{checkmark, csv_table},
+};
+use serde::{Deserialize, Serialize};
+use std::fmt::Display;
+use strum::IntoEnumIterator;
+use strum_macros::EnumIter;
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, Default, PartialEq, EnumIter)]
+pub enum Class {
+    #[default]
+    Warrior,
+    Ranger,
+    Mage,
+    Rogue,
+}
+
+impl Display for Class {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{self:?}")
+    }
+}
+
+impl Class {
+    pub fn description(self) -> &'static str {
+        match self {
+            Class::Warrior => "A sturdy fighter who hits hard and forges well",
+            Class::Ranger => "A sharp-eyed hunter at home in the wilds",
+            Class::Mage => "A spellcaster whose magic grows with intelligence",
+            Class::Rogue => "A quick blade with light fingers",
+        }
+    }
+
+    /// The class skill tree, in the order skills unlock.
+    pub fn skills(self) -> Vec<Skill> {
+        Skill::iter().filter(|skill| skill.class() == self).collect()
+    }
+
+    pub fn table(self, player: &Player) {
+        let mut strings: Vec<String> = vec!["Skill,Combat Level,Type,Effect,Unlocked?".to_string()];
+
+        for skill in self.skills() {
+            let kind: &str = if skill.ability().is_some() { "Active" } else { "Passive" };
+
+            strings.push(format!(
+                "{skill},{},{kind},{},{}",
+                skill.level(),
+                skill.effect(),
+                checkmark(has(player, skill))
+            ));
+        }
+
+        csv_table(&strings);
+    }
+}
+
+/// What an active skill does when used in battle.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Ability {
+    /// A weapon attack dealing a percentage of normal damage.
+    Strike { percent: usize, sure_hit: bool },
+
+    /// A spell that never misses and needs no weapon, dealing its base
+    /// damage plus 3 for every point of intelligence.
+    Spell(usize),
+
+    /// Heals a percentage of max hp instead of attacking.
+    Heal(usize),
+
+    /// Negates the enemy's next attack instead of attacking.
+    Shield,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, EnumIter)]
+pub enum Skill {
+    PowerStrike,
+    IronSkin,
+    SecondWind,
+    Forgemaster,
+
+    AimedShot,
+    Outdoorsman,
+    KeenEye,
+    Volley,
+
+    Fireball,
+    Alchemist,
+    ManaShield,
+    Meteor,
+
+    Backstab,
+    LightFingers,
+    Evasion,
+    Assassin,
+}
+
+impl Display for Skill {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Skill::PowerStrike => write!(f, "Power Strike"),
+            Skill::IronSkin => write!(f, "Iron Skin"),
+            Skill::SecondWind => write!(f, "Second Wind"),
+            Skill::AimedShot => write!(f, "Aimed Shot"),
+            Skill::KeenEye => write!(f, "Keen Eye"),
+            Skill::ManaShield => write!(f, "Mana Shield"),
+            Skill::LightFingers => write!(f, "Light Fingers"),
+            other => write!(f, "{other:?}"),
+        }
+    }
+}
+
+impl Skill {
+    pub fn class(self) -> Class {
+        match self {
+            Skill::PowerStrike | Skill::IronSkin | Skill::SecondWind | Skill::Forgemaster => Class::Warrior,
+            Skill::AimedShot | Skill::Outdoorsman | Skill::KeenEye | Skill::Volley => Class::Ranger,
+            Skill::Fireball | Skill::Alchemist | Skill::ManaShield | Skill::Meteor => Class::Mage,
+            Skill::Backstab | Skill::LightFingers | Skill::Evasion | Skill::Assassin => Class::Rogue,
+        }
+    }
+
+    /// Combat level that unlocks the skill.
+    pub fn level(self) -> usize {
+        match self {
+            Skill::PowerStrike | Skill::AimedShot | Skill::Fireball | Skill::Backstab => 1,
+            Skill::IronSkin | Skill::Outdoorsman | Skill::Alchemist | Skill::LightFingers => 5,
+            Skill::SecondWind | Skill::KeenEye | Skill::ManaShield | Skill::Evasion => 10,
+            Skill::Forgemaster | Skill::Volley | Skill::Meteor | Skill::Assassin => 20,
+        }
+    }
+
+    /// The ability used in battle, or None for passive skills.
+    pub fn ability(self) -> Option<Ability> {
+        let ability = match self {
+            Skill::PowerStrike | Skill::Backstab => Ability::Strike {
+                percent: 200,
+                sure_hit: false,
+            },
+            Skill::AimedShot => Ability::Strike {
+                percent: 150,
+                sure_hit: true,
+            },
+            Skill::Volley => Ability::Strike {
+                percent: 300,
+                sure_hit: false,
+            },
+            Skill::Fireball => Ability::Spell(10),
+            Skill::Meteor => Ability::Spell(30),
+            Skill::SecondWind => Ability::Heal(30),
+            Skill::ManaShield | Skill::Evasion => Ability::Shield,
+            _ => return None,
+        };
+
+        Some(ability)
+    }
+
+    pub fn effect(self) -> &'static str {
+        match self {
+            Skill::PowerStrike | Skill::Backstab => "Attack for double damage",
+            Skill::IronSkin => "+5 defense",
+            Skill::SecondWind => "Heal 30% of max hp",
+            Skill::Forgemaster => "+1 yield when mining and smithing",
+            Skill::AimedShot => "An attack that never misses for 1.5x damage",
+            Skill::Outdoorsman => "+1 yield when fishing and woodcutting, +1 furs when hunting",
+            Skill::KeenEye => "+10% hit chance",
+            Skill::Volley => "Attack for triple damage",
+            Skill::Fireball => "A spell for 10 damage, +3 per intelligence",
+            Skill::Alchemist => "+1 yield when cooking",
+            Skill::ManaShield | Skill::Evasion => "Negate the enemy's next attack",
+            Skill::Meteor => "A spell for 30 damage, +3 per intelligence",
+            Skill::LightFingers => "+10% thieving success, +25% stolen gold",
+            Skill::Assassin => "+10% critical chance",
+        }
+    }
+}
+
+/// Whether the player's class has the skill and it is unlocked.
+pub fn has(player: &Player, skill: Skill) -> bool {
+    player.class == skill.class() && XP::get_level(player.xp.combat) >= skill.level()
+}
+
+/// Unlocked skills with abilities usable in battle.
+pub fn abilities(player: &Player) -> Vec<Skill> {
+    player
+        .class
+        .skills()
+        .into_iter()
+        .filter(|skill| skill.ability().is_some() && has(player, *skill))
+        .collect()
+}
+
+/// Skills unlocked at exactly the given combat level.
+pub fn unlocked_at(player: &Player, level: usize) -> Vec<Skill> {
+    player
+        .class
+        .skills()
+        .into_iter()
+        .filter(|skill| skill.level() == level)
+        .collect()
+}
+
+pub fn bonus_defense(player: &Player) -> usize {
+    if has(player, Skill::IronSkin) {
+        5
+    } else {
+        0
+    }
+}
+
+/// Percent chance to hit, including attributes and class passives.
+pub fn hit_chance(player: &Player) -> usize {
+    let bonus: usize = if has(player, Skill::KeenEye) { 10 } else { 0 };
+
+    (player.attributes.hit_chance() + bonus).min(MAX_HIT_CHANCE)
+}
+
+pub fn bonus_critical_chance(player: &Player) -> usize {
+    if has(player, Skill::Assassin) {
+        10
+    } else {
+        0
+    }
+}
+
+/// Extra items produced by a successful shift of guild work.
+pub fn bonus_yield(player: &Player, skill: XPType) -> usize {
+    let passive: Skill = match skill {
+        XPType::Mining | XPType::Smithing => Skill::Forgemaster,
+        XPType::Fishing | XPType::Woodcutting | XPType::Hunting => Skill::Outdoorsman,
+        XPType::Cooking => Skill::Alchemist,
+        _ => return 0,
+    };
+
+    usize::from(has(player, passive))
+}
+
+pub fn bonus_thieving_chance(player: &Player) -> usize {
+    if has(player, Skill::LightFingers) {
+        10
+    } else {
+        0
+    }
+}
+
+/// Stolen gold raised by class passives.
+pub fn stolen_gold(player: &Player, gold: usize) -> usize {
+    if has(player, Skill::LightFingers) {
+        gold + gold / 4
+    } else {
+        gold
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn skills_unlock_with_combat_level() {
+        use super::{abilities, bonus_yield, has, Class, Skill};
+        use crate::data::{catalog::Catalog, player::Player, xp::XPType};
+
+        let mut player = Player {
+            class: Class::Mage,
+            ..Default::default()
+        };
+
+        assert_eq!(abilities(&player), vec![Skill::Fireball]);
+        assert!(!has(&player, Skill::PowerStrike));
+
+        player.xp.combat = Catalog::get().levels.xp_for(20);
+
+        assert_eq!(
+            abilities(&player),
+            vec![Skill::Fireball, Skill::ManaShield, Skill::Meteor]
+        );
+        assert_eq!(bonus_yield(&player, XPType::Cooking), 1);
+        assert_eq!(bonus_yield(&player, XPType::Mining), 0);
+    }
+}
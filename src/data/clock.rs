@@ -0,0 +1,46 @@
+/*!
+# In-Game Clock
+
+Time in Albion passes in hours as the player acts: every battle and every
+shift of guild work takes an hour. Systems that change over time, like the
+Trading Post market, are updated whenever a new day begins.
+*/
+use serde::{Deserialize, Serialize};
+use std::fmt::Display;
+
+pub const HOURS_PER_DAY: usize = 24;
+
+/// Hour of the first day a new profile starts at.
+const STARTING_HOUR: usize = 8;
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct Clock {
+    pub hours: usize,
+}
+
+impl Default for Clock {
+    fn default() -> Self {
+        Self { hours: STARTING_HOUR }
+    }
+}
+
+impl Display for Clock {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Day {}, {:02}:00", self.day(), self.hours % HOURS_PER_DAY)
+    }
+}
+
+impl Clock {
+    /// Current day, starting at day 1.
+    pub fn day(&self) -> usize {
+        self.hours / HOURS_PER_DAY + 1
+    }
+
+    /// Move the clock forward, returning how many new days began.
+    pub fn advance(&mut self, hours: usize) -> usize {
+        let day: usize = self.day();
+
+        self.hours += hours;
+        self.day() - day
+    }
+}
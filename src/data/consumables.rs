@@ -0,0 +1,224 @@
+/*!
+# Consumables
+
+Potions bought from the Trading Post (or brewed) and drunk in or out of
+battle. Healing potions never raise hp above the max. Other potions give
+an effect that lasts for a number of in-game hours, and drinking one again
+while it is active starts the duration over.
+
+# Potions
+
+- Minor Healing: Heals 20-30 hp
+- Major Healing: Heals 50-75 hp
+- Strength: +10 damage for 3 hours
+- Defense: +10 defense for 3 hours
+- Antidote: Cures poison
+- Elixir of Learning: +50% xp for 6 hours
+*/
+use crate::{
+    data::{inventory::items, player::Player},
+    prelude::{csv_table, error, random_num},
+};
+use serde::{Deserialize, Serialize};
+use std::{collections::BTreeMap, fmt::Display};
+use strum::IntoEnumIterator;
+use strum_macros::EnumIter;
+
+/// Damage added by a strength potion.
+pub const STRENGTH_BONUS: usize = 10;
+
+/// Defense added by a defense potion.
+pub const DEFENSE_BONUS: usize = 10;
+
+#[derive(Serialize, Deserialize, PartialEq, PartialOrd, Eq, Ord, Clone, Copy, Debug, EnumIter)]
+pub enum Consumable {
+    MinorHealing,
+    MajorHealing,
+    Strength,
+    Defense,
+    Antidote,
+    XPBoost,
+}
+
+impl Display for Consumable {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.item())
+    }
+}
+
+impl Consumable {
+    pub fn item(self) -> items::Types {
+        match self {
+            Consumable::MinorHealing => items::Types::Potions,
+            Consumable::MajorHealing => items::Types::StrongPotions,
+            Consumable::Strength => items::Types::StrengthPotions,
+            Consumable::Defense => items::Types::DefensePotions,
+            Consumable::Antidote => items::Types::Antidotes,
+            Consumable::XPBoost => items::Types::XPBoostPotions,
+        }
+    }
+
+    pub fn effect(self) -> &'static str {
+        match self {
+            Consumable::MinorHealing => "Heals 20-30 hp",
+            Consumable::MajorHealing => "Heals 50-75 hp",
+            Consumable::Strength => "+10 damage",
+            Consumable::Defense => "+10 defense",
+            Consumable::Antidote => "Cures poison",
+            Consumable::XPBoost => "+50% xp",
+        }
+    }
+
+    /// In-game hours the effect lasts, or None for potions that act at once.
+    pub fn duration(self) -> Option<usize> {
+        match self {
+            Consumable::Strength | Consumable::Defense => Some(3),
+            Consumable::XPBoost => Some(6),
+            _ => None,
+        }
+    }
+}
+
+/// Hour each active potion effect wears off.
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
+#[serde(transparent)]
+pub struct Effects {
+    pub expires: BTreeMap<Consumable, usize>,
+}
+
+impl Effects {
+    pub fn reset(&mut self) {
+        *self = Self::default();
+    }
+
+    pub fn is_active(&self, consumable: Consumable, hour: usize) -> bool {
+        self.expires.get(&consumable).is_some_and(|expires| *expires > hour)
+    }
+
+    /// Drop effects that have worn off.
+    pub fn expire(player: &mut Player) {
+        let hour: usize = player.clock.hours;
+
+        player.effects.expires.retain(|_, expires| *expires > hour);
+    }
+
+    pub fn table(player: &Player) {
+        let mut strings: Vec<String> = vec!["Potion,Effect,Duration,Quantity,Active".to_string()];
+
+        for consumable in Consumable::iter() {
+            let duration: String = consumable
+                .duration()
+                .map_or_else(|| "-".to_string(), |hours| format!("{hours} Hours"));
+
+            let active: String = player.effects.expires.get(&consumable).map_or_else(
+                || "-".to_string(),
+                |expires| format!("{} Hours Left", expires.saturating_sub(player.clock.hours)),
+            );
+
+            strings.push(format!(
+                "{consumable},{},{duration},{},{active}",
+                consumable.effect(),
+                player.items.quantity(consumable.item())
+            ));
+        }
+
+        csv_table(&strings);
+    }
+
+    /// Drink a potion, returning a message describing what it did.
+    pub fn consume(player: &mut Player, consumable: Consumable) -> error::Result<String> {
+        if player.items.quantity(consumable.item()) == 0 {
+            return Err(Box::new(error::Inventory::NotEnoughItem(consumable.to_string())));
+        }
+
+        let max_hp: usize = player.max_hp();
+
+        let message: String = match consumable {
+            Consumable::MinorHealing | Consumable::MajorHealing => {
+                if player.health.hp >= max_hp {
+                    return Err(Box::new(error::Miscellaneous::Custom("Your health is already full.")));
+                }
+
+                let (min, max) = if consumable == Consumable::MinorHealing {
+                    (20, 30)
+                } else {
+                    (50, 75)
+                };
+                let healed: usize = random_num(min, max).min(max_hp - player.health.hp);
+
+                player.health.hp += healed;
+                format!("Your health increased {healed} hp, and is now {}.", player.health.hp)
+            }
+            Consumable::Antidote => {
+                if player.health.poison == 0 {
+                    return Err(Box::new(error::Miscellaneous::Custom("You are not poisoned.")));
+                }
+
+                player.health.poison = 0;
+                "The poison has been cured.".to_string()
+            }
+            Consumable::Strength | Consumable::Defense | Consumable::XPBoost => {
+                let hours: usize = consumable.duration().unwrap_or_default();
+
+                player.effects.expires.insert(consumable, player.clock.hours + hours);
+                Self::expire(player);
+
+                format!("{} for the next {hours} hours.", consumable.effect())
+            }
+        };
+
+        *player.items.get(consumable.item()) -= 1;
+        player.statistics.drink(consumable);
+
+        Ok(message)
+    }
+}
+
+pub fn bonus_damage(player: &Player) -> usize {
+    if player.effects.is_active(Consumable::Strength, player.clock.hours) {
+        STRENGTH_BONUS
+    } else {
+        0
+    }
+}
+
+pub fn bonus_defense(player: &Player) -> usize {
+    if player.effects.is_active(Consumable::Defense, player.clock.hours) {
+        DEFENSE_BONUS
+    } else {
+        0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn healing_is_capped_and_effects_expire() {
+        use super::{Consumable, Effects};
+        use crate::data::{
+            inventory::items,
+            player::Player,
+            xp::{XPType, XP},
+        };
+
+        let mut player = Player::default();
+
+        *player.items.get(items::Types::Potions) += 2;
+        *player.items.get(items::Types::XPBoostPotions) += 1;
+
+        assert!(Effects::consume(&mut player, Consumable::MinorHealing).is_err());
+
+        player.health.hp = player.max_hp() - 5;
+        assert!(Effects::consume(&mut player, Consumable::MinorHealing).is_ok());
+        assert_eq!(player.health.hp, player.max_hp());
+
+        assert!(Effects::consume(&mut player, Consumable::XPBoost).is_ok());
+        assert!(XP::add(&mut player, XPType::Fishing, 10).is_ok());
+        assert_eq!(player.xp.fishing, 15);
+
+        player.pass_time(6);
+        assert!(XP::add(&mut player, XPType::Fishing, 10).is_ok());
+        assert_eq!(player.xp.fishing, 25);
+        assert!(player.effects.expires.is_empty());
+    }
+}
@@ -0,0 +1,113 @@
+/*!
+# Crafting
+
+Turns gathered materials into new items and gear. Recipes are listed in
+the item catalog along with the skill and level required to craft them,
+and crafting a recipe earns xp in that skill.
+
+# Recipes
+
+- Cooked Meat: Raw Meat from hunting (Cooking)
+- Minor Healing Potion: Herbs from the farm (Cooking)
+- Major Healing Potion: Bones and Minor Healing Potions (Cooking)
+- Leather Gloves and Cap: Furs (Smithing)
+- Dragonhide Armor: Dragon Hides (Smithing)
+- Mystic Ring: Runic Tablets, Rubies, and Magic Scrolls (Smithing)
+*/
+use crate::{
+    data::{
+        catalog::{Catalog, OutputKind, RecipeDef},
+        player::Player,
+        xp::XP,
+    },
+    prelude::{checkmark, csv_table, error},
+};
+
+/// Inputs of a recipe written out, like `3 Bones + 2 Potions`.
+pub fn inputs(recipe: &RecipeDef) -> String {
+    let catalog = Catalog::get();
+    let inputs: Vec<String> = recipe
+        .inputs
+        .iter()
+        .map(|(id, quantity)| format!("{quantity} {}", catalog.item_name(id)))
+        .collect();
+
+    inputs.join(" + ")
+}
+
+/// Name of whatever the recipe makes.
+pub fn output(recipe: &RecipeDef) -> String {
+    let catalog = Catalog::get();
+    let name: String = match recipe.output.kind {
+        OutputKind::Item => catalog.item_name(&recipe.output.id),
+        OutputKind::Weapon => catalog
+            .weapon(&recipe.output.id)
+            .map_or_else(|| recipe.output.id.clone(), |weapon| weapon.name.clone()),
+        OutputKind::Armor => catalog
+            .armor(&recipe.output.id)
+            .map_or_else(|| recipe.output.id.clone(), |armor| armor.name.clone()),
+    };
+
+    format!("{} {name}", recipe.output.quantity)
+}
+
+/// Checks the player has the level and materials for a recipe, and doesn't already own the gear it makes.
+pub fn can_craft(player: &mut Player, recipe: &RecipeDef) -> error::Result<()> {
+    if XP::get_level(*player.xp.get(recipe.skill)) < recipe.level {
+        return Err(Box::new(error::Inventory::NotEnoughXP));
+    }
+
+    for (id, quantity) in &recipe.inputs {
+        if player.items.quantity(id) < *quantity {
+            return Err(Box::new(error::Inventory::NotEnoughItem(Catalog::get().item_name(id))));
+        }
+    }
+
+    let owns: bool = match recipe.output.kind {
+        OutputKind::Item => false,
+        OutputKind::Weapon => player.weapons.get(&recipe.output.id).owns,
+        OutputKind::Armor => player.armor.get(&recipe.output.id).owns,
+    };
+
+    if owns {
+        return Err(Box::new(error::Inventory::ItemOwned));
+    }
+
+    Ok(())
+}
+
+/// Consume the recipe's inputs and give the player what it makes.
+pub fn craft(player: &mut Player, recipe: &RecipeDef) -> error::Result<()> {
+    can_craft(player, recipe)?;
+
+    for (id, quantity) in &recipe.inputs {
+        *player.items.get(id) -= quantity;
+    }
+
+    match recipe.output.kind {
+        OutputKind::Item => *player.items.get(&recipe.output.id) += recipe.output.quantity,
+        OutputKind::Weapon => player.weapons.get(&recipe.output.id).owns = true,
+        OutputKind::Armor => player.armor.get(&recipe.output.id).owns = true,
+    }
+
+    XP::add(player, recipe.skill, recipe.xp)?;
+    Ok(())
+}
+
+pub fn table(player: &mut Player) {
+    let mut strings: Vec<String> = vec!["Recipe,Inputs,Makes,Skill,Level,Craftable".to_string()];
+
+    for recipe in &Catalog::get().recipes {
+        strings.push(format!(
+            "{},{},{},{},{},{}",
+            recipe.name,
+            inputs(recipe),
+            output(recipe),
+            recipe.skill,
+            recipe.level,
+            checkmark(can_craft(player, recipe).is_ok())
+        ));
+    }
+
+    csv_table(&strings);
+}
@@ -0,0 +1,237 @@
+/*!
+# Farming
+
+Members of the Farming guild tend a few crop plots. Plots are planted with
+seeds and grow as in-game time passes. Ripe crops are harvested for bread,
+or for herbs that can be brewed into potions.
+
+# Crops
+
+- Wheat: 1 seed, ripe in 12 hours, harvested as bread
+- Herbs: 2 seeds, ripe in 24 hours, farming level 3
+*/
+use crate::{
+    data::{
+        guilds::Guild,
+        inventory::items,
+        player::Player,
+        xp::{XPType, XP},
+    },
+    prelude::{csv_table, error, random_num},
+};
+use serde::{Deserialize, Serialize};
+use std::fmt::Display;
+use strum_macros::EnumIter;
+
+/// Number of crop plots on the farm.
+pub const PLOTS: usize = 4;
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, EnumIter)]
+pub enum Crop {
+    Wheat,
+    Herbs,
+}
+
+impl Display for Crop {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{self:?}")
+    }
+}
+
+impl Crop {
+    /// Seeds needed to plant one plot.
+    pub fn seeds(self) -> usize {
+        match self {
+            Crop::Wheat => 1,
+            Crop::Herbs => 2,
+        }
+    }
+
+    pub fn growth_hours(self) -> usize {
+        match self {
+            Crop::Wheat => 12,
+            Crop::Herbs => 24,
+        }
+    }
+
+    /// Farming level needed to plant the crop.
+    pub fn level(self) -> usize {
+        match self {
+            Crop::Wheat => 1,
+            Crop::Herbs => 3,
+        }
+    }
+
+    pub fn produce(self) -> items::Types {
+        match self {
+            Crop::Wheat => items::Types::Food,
+            Crop::Herbs => items::Types::Herbs,
+        }
+    }
+
+    /// Farming xp earned from a harvest.
+    pub fn xp(self) -> usize {
+        match self {
+            Crop::Wheat => 5,
+            Crop::Herbs => 12,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
+pub struct Plot {
+    pub crop: Option<Crop>,
+
+    /// In-game hour the crop was planted.
+    pub planted: usize,
+}
+
+impl Plot {
+    /// Hours until the crop is ripe, or None if nothing is planted.
+    pub fn hours_left(&self, now: usize) -> Option<usize> {
+        self.crop
+            .map(|crop| (self.planted + crop.growth_hours()).saturating_sub(now))
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct Farm {
+    pub plots: Vec<Plot>,
+}
+
+impl Default for Farm {
+    fn default() -> Self {
+        Self {
+            plots: vec![Plot::default(); PLOTS],
+        }
+    }
+}
+
+impl Farm {
+    pub fn reset(&mut self) {
+        *self = Self::default();
+    }
+
+    pub fn table(&self, now: usize) {
+        let mut strings: Vec<String> = vec!["Plot,Crop,Status".to_string()];
+
+        for (index, plot) in self.plots.iter().enumerate() {
+            let status: String = match plot.hours_left(now) {
+                None => "Empty".to_string(),
+                Some(0) => "Ripe".to_string(),
+                Some(hours) => format!("{hours} hours left"),
+            };
+
+            strings.push(format!(
+                "{},{},{status}",
+                index + 1,
+                plot.crop.map_or_else(|| "-".to_string(), |crop| crop.to_string())
+            ));
+        }
+
+        csv_table(&strings);
+    }
+
+    pub fn plant(player: &mut Player, plot: usize, crop: Crop) -> error::Result<()> {
+        if XP::get_level(player.xp.farming) < crop.level() {
+            return Err(Box::new(error::Inventory::NotEnoughXP));
+        }
+
+        let now: usize = player.clock.hours;
+        let Some(target) = player.farm.plots.get_mut(plot) else {
+            return Err(Box::new(error::Inventory::ItemNotExist));
+        };
+
+        if target.crop.is_some() {
+            return Err(Box::new(error::Miscellaneous::Custom("That plot is already planted.")));
+        }
+
+        let seeds = player.items.get(items::Types::Seeds);
+
+        if *seeds < crop.seeds() {
+            return Err(Box::new(error::Inventory::NotEnoughItem(
+                items::Types::Seeds.to_string(),
+            )));
+        }
+
+        *seeds -= crop.seeds();
+        *target = Plot {
+            crop: Some(crop),
+            planted: now,
+        };
+
+        XP::add(player, XPType::Farming, 1)
+    }
+
+    /// Harvest a ripe plot, returning the crop and how much it yielded.
+    pub fn harvest(player: &mut Player, plot: usize) -> error::Result<(Crop, usize)> {
+        let now: usize = player.clock.hours;
+        let Some(target) = player.farm.plots.get_mut(plot) else {
+            return Err(Box::new(error::Inventory::ItemNotExist));
+        };
+
+        let Some(crop) = target.crop else {
+            return Err(Box::new(error::Miscellaneous::Custom("Nothing is planted there.")));
+        };
+
+        if target.hours_left(now) != Some(0) {
+            return Err(Box::new(error::Miscellaneous::Custom("That crop is not ripe yet.")));
+        }
+
+        *target = Plot::default();
+
+        let harvested: usize = random_num(2, 4) + XP::get_level(player.xp.farming) / 10;
+
+        *player.items.get(crop.produce()) += harvested;
+        player.statistics.gather(Guild::Farming, harvested);
+        XP::add(player, XPType::Farming, crop.xp())?;
+
+        Ok((crop, harvested))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn planting_uses_seeds_and_needs_a_level() {
+        use super::{Crop, Farm};
+        use crate::data::{inventory::items, player::Player};
+
+        let mut player = Player::default();
+
+        assert!(Farm::plant(&mut player, 0, Crop::Wheat).is_err());
+
+        *player.items.get(items::Types::Seeds) += 3;
+
+        assert!(Farm::plant(&mut player, 0, Crop::Herbs).is_err());
+        assert!(Farm::plant(&mut player, 0, Crop::Wheat).is_ok());
+        assert!(Farm::plant(&mut player, 0, Crop::Wheat).is_err());
+        assert!(Farm::plant(&mut player, super::PLOTS, Crop::Wheat).is_err());
+        assert_eq!(player.items.quantity(items::Types::Seeds.id()), 2);
+    }
+
+    #[test]
+    fn only_ripe_crops_are_harvested() {
+        use super::{Crop, Farm};
+        use crate::data::{inventory::items, player::Player};
+
+        let mut player = Player::default();
+        *player.items.get(items::Types::Seeds) += 1;
+
+        assert!(Farm::harvest(&mut player, 0).is_err());
+        assert!(Farm::plant(&mut player, 0, Crop::Wheat).is_ok());
+
+        player.clock.hours += Crop::Wheat.growth_hours() - 1;
+        assert!(Farm::harvest(&mut player, 0).is_err());
+
+        player.clock.hours += 1;
+        let harvest = Farm::harvest(&mut player, 0);
+
+        assert!(harvest.is_ok());
+        assert_eq!(
+            player.items.quantity(items::Types::Food.id()),
+            harvest.map(|(_, yielded)| yielded).unwrap_or_default()
+        );
+        assert!(player.farm.plots[0].crop.is_none());
+    }
+}
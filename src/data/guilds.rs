@@ -1,10 +1,34 @@
-use crate::prelude::{checkmark, csv_table, error, select};
-use crate::{data::player::Player, panic_menu};
+/*!
+# Guilds
 
-use serde::{Deserialize, Serialize};
+Guild memberships are bought from the Memberships Office. Members rise from
+Apprentice to Grandmaster by leveling the guild's skill and delivering the
+contracts posted on its quest board each day. Higher ranks get bigger
+contracts and a discount on the guild's goods and tools.
+*/
+use crate::prelude::{checkmark, csv_table, error, random_num, select};
+use crate::{
+    data::{
+        catalog::Catalog,
+        inventory::{
+            bank::Account,
+            items,
+            ledger::{Ledger, Source},
+            market::Market,
+        },
+        player::Player,
+        thieving,
+        xp::{XPType, XP},
+    },
+    panic_menu,
+};
+
+use serde::{Deserialize, Deserializer, Serialize};
 use std::{collections::BTreeMap, fmt::Display};
+use strum::IntoEnumIterator;
+use strum_macros::EnumIter;
 
-#[derive(PartialEq, PartialOrd, Eq, Ord, Clone, Copy, Debug)]
+#[derive(Serialize, Deserialize, PartialEq, PartialOrd, Eq, Ord, Clone, Copy, Debug, EnumIter)]
 pub enum Guild {
     Fishing,
     Cooking,
@@ -12,6 +36,8 @@ pub enum Guild {
     Mining,
     Smithing,
     Thieving,
+    Farming,
+    Hunting,
 }
 
 impl Display for Guild {
@@ -26,47 +52,274 @@ impl Display for Guild {
                 Guild::Woodcutting => "Woodcutting",
                 Guild::Smithing => "Smithing",
                 Guild::Thieving => "Thieving",
+                Guild::Farming => "Farming",
+                Guild::Hunting => "Hunting",
             }
         )
     }
 }
 
+impl Guild {
+    /// Skill trained by working at the guild.
+    pub fn skill(self) -> XPType {
+        match self {
+            Guild::Fishing => XPType::Fishing,
+            Guild::Cooking => XPType::Cooking,
+            Guild::Woodcutting => XPType::Woodcutting,
+            Guild::Mining => XPType::Mining,
+            Guild::Smithing => XPType::Smithing,
+            Guild::Thieving => XPType::Thieving,
+            Guild::Farming => XPType::Farming,
+            Guild::Hunting => XPType::Hunting,
+        }
+    }
+
+    /// Items the guild trades in. Contracts ask for these, and members buy them at a discount.
+    pub fn goods(self) -> &'static [items::Types] {
+        match self {
+            Guild::Fishing => &[items::Types::Fish, items::Types::Bait],
+            Guild::Cooking => &[items::Types::CookedFish],
+            Guild::Woodcutting => &[items::Types::Wood],
+            Guild::Mining => &[items::Types::Ore],
+            Guild::Smithing => &[items::Types::Ingots],
+            Guild::Thieving => &[items::Types::MagicScrolls, items::Types::Rubies],
+            Guild::Farming => &[items::Types::Herbs, items::Types::Seeds],
+            Guild::Hunting => &[items::Types::Furs, items::Types::Meat],
+        }
+    }
+}
+
+/// Contracts posted on each guild's quest board every day.
+pub const BOARD_SIZE: usize = 3;
+
+#[derive(Serialize, Deserialize, PartialEq, PartialOrd, Eq, Ord, Clone, Copy, Debug, Default, EnumIter)]
+pub enum Rank {
+    #[default]
+    Apprentice,
+    Journeyman,
+    Expert,
+    Master,
+    Grandmaster,
+}
+
+impl Display for Rank {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{self:?}")
+    }
+}
+
+impl Rank {
+    /// Guild level and delivered contracts needed to reach the rank.
+    pub fn requirements(self) -> (usize, usize) {
+        match self {
+            Rank::Apprentice => (1, 0),
+            Rank::Journeyman => (5, 2),
+            Rank::Expert => (15, 5),
+            Rank::Master => (30, 10),
+            Rank::Grandmaster => (50, 20),
+        }
+    }
+
+    /// Percent off the guild's goods and tools.
+    pub fn discount(self) -> usize {
+        match self {
+            Rank::Apprentice => 0,
+            Rank::Journeyman => 5,
+            Rank::Expert => 10,
+            Rank::Master => 15,
+            Rank::Grandmaster => 20,
+        }
+    }
+
+    /// Highest rank a member with the level and contracts has earned.
+    pub fn earned(level: usize, contracts: usize) -> Self {
+        Rank::iter()
+            .rev()
+            .find(|rank| {
+                let (required_level, required_contracts) = rank.requirements();
+                level >= required_level && contracts >= required_contracts
+            })
+            .unwrap_or_default()
+    }
+
+    pub fn next(self) -> Option<Self> {
+        Rank::iter().find(|rank| *rank > self)
+    }
+}
+
+/// A request on a quest board to deliver goods to the guild.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct Contract {
+    pub item: String,
+    pub quantity: usize,
+    pub gold: usize,
+    pub xp: usize,
+}
+
+impl Display for Contract {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "Deliver {} {},{} Gold,{} XP",
+            self.quantity,
+            Catalog::get().item_name(&self.item),
+            self.gold,
+            self.xp
+        )
+    }
+}
+
+impl Contract {
+    /**
+    A contract for the guild's goods, asking for more from higher ranks.
+
+    Gold is paid at the lowest price the goods could ever be bought for at the
+    Trading Post, after the best guild discount, so buying the goods to deliver
+    them never turns a profit. Contracts are worth it for the xp and rank.
+     */
+    pub fn generate(guild: Guild, rank: Rank) -> Self {
+        let goods: &[items::Types] = guild.goods();
+        let item: items::Types = goods[random_num(0, goods.len() - 1)];
+        let quantity: usize = random_num(5, 20) + rank as usize * 5;
+        let floor: usize = Market::floor_price(item.id()).unwrap_or(0) * quantity;
+
+        Self {
+            item: item.id().to_string(),
+            quantity,
+            gold: floor - floor * Rank::Grandmaster.discount() / 100,
+            xp: quantity * 2,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
+pub struct Membership {
+    pub rank: Rank,
+
+    /// Contracts delivered to the guild.
+    pub contracts: usize,
+    pub board: Vec<Contract>,
+}
+
+impl Membership {
+    pub fn new(guild: Guild) -> Self {
+        let mut membership = Self::default();
+
+        membership.post_contracts(guild);
+        membership
+    }
+
+    /// Replace the quest board with fresh contracts.
+    pub fn post_contracts(&mut self, guild: Guild) {
+        self.board = (0..BOARD_SIZE).map(|_| Contract::generate(guild, self.rank)).collect();
+    }
+}
+
+/// Guilds as saved in a profile. Profiles from before ranks saved a flag for
+/// each guild, and join as apprentices of the guilds they belonged to.
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum Saved {
+    Memberships(BTreeMap<Guild, Membership>),
+    Flags(BTreeMap<String, bool>),
+}
+
+fn migrate<'de, D: Deserializer<'de>>(deserializer: D) -> Result<BTreeMap<Guild, Membership>, D::Error> {
+    match Saved::deserialize(deserializer)? {
+        Saved::Memberships(memberships) => Ok(memberships),
+        Saved::Flags(flags) => Ok(Guild::iter()
+            .filter(|guild| flags.get(&format!("{guild:?}").to_lowercase()) == Some(&true))
+            .map(|guild| (guild, Membership::new(guild)))
+            .collect()),
+    }
+}
+
+/// Memberships keyed by guild. Players are members of the guilds present.
 #[derive(Serialize, Deserialize, Debug, Clone, Default)]
+#[serde(transparent)]
 pub struct Guilds {
-    pub thieving: bool,
-    pub fishing: bool,
-    pub cooking: bool,
-    pub woodcutting: bool,
-    pub mining: bool,
-    pub smithing: bool,
+    #[serde(deserialize_with = "migrate")]
+    pub memberships: BTreeMap<Guild, Membership>,
 }
 
 impl Guilds {
-    pub fn get<'a>(&'a mut self, guild: &Guild) -> &'a mut bool {
-        match guild {
-            Guild::Thieving => &mut self.thieving,
-            Guild::Cooking => &mut self.cooking,
-            Guild::Fishing => &mut self.fishing,
-            Guild::Mining => &mut self.mining,
-            Guild::Smithing => &mut self.smithing,
-            Guild::Woodcutting => &mut self.woodcutting,
+    pub fn get(&mut self, guild: Guild) -> Option<&mut Membership> {
+        self.memberships.get_mut(&guild)
+    }
+
+    pub fn check(&self, guild: Guild) -> bool {
+        self.memberships.contains_key(&guild)
+    }
+
+    pub fn rank(&self, guild: Guild) -> Option<Rank> {
+        self.memberships.get(&guild).map(|membership| membership.rank)
+    }
+
+    /// Percent off an item from the best ranked guild that trades in it.
+    pub fn item_discount(&self, id: &str) -> usize {
+        self.memberships
+            .iter()
+            .filter(|(guild, _)| guild.goods().iter().any(|item| item.id() == id))
+            .map(|(_, membership)| membership.rank.discount())
+            .max()
+            .unwrap_or(0)
+    }
+
+    /// Post new contracts on every quest board, once per day.
+    pub fn new_days(&mut self, days: usize) {
+        if days == 0 {
+            return;
+        }
+
+        for (guild, membership) in &mut self.memberships {
+            membership.post_contracts(*guild);
         }
     }
 
-    pub fn check(&mut self, guild: Guild) -> bool {
-        match guild {
-            Guild::Thieving => self.thieving,
-            Guild::Cooking => self.cooking,
-            Guild::Fishing => self.fishing,
-            Guild::Mining => self.mining,
-            Guild::Smithing => self.smithing,
-            Guild::Woodcutting => self.woodcutting,
+    /// Promote the player if they've earned a higher rank, returning the new rank.
+    pub fn promote(player: &mut Player, guild: Guild) -> Option<Rank> {
+        let level: usize = XP::get_level(*player.xp.get(guild.skill()));
+        let membership = player.guilds.get(guild)?;
+        let earned: Rank = Rank::earned(level, membership.contracts);
+
+        if earned <= membership.rank {
+            return None;
         }
+
+        membership.rank = earned;
+        Some(earned)
     }
 
-    pub fn toggle(&mut self, guild: Guild) {
-        let guild = self.get(&guild);
-        *guild = !*guild;
+    /// Deliver the goods for a contract on the quest board, returning the new
+    /// rank if the delivery earned a promotion.
+    pub fn deliver(player: &mut Player, guild: Guild, index: usize) -> error::Result<Option<Rank>> {
+        let Some(contract) = player
+            .guilds
+            .get(guild)
+            .and_then(|membership| membership.board.get(index))
+            .cloned()
+        else {
+            return Err(Box::new(error::Miscellaneous::Custom(
+                "That contract is no longer posted.",
+            )));
+        };
+
+        if player.items.quantity(&contract.item) < contract.quantity {
+            return Err(Box::new(error::Inventory::NotEnoughItem(
+                Catalog::get().item_name(&contract.item),
+            )));
+        }
+
+        *player.items.get(&contract.item) -= contract.quantity;
+        Ledger::credit(player, Source::Guilds, Account::Wallet, contract.gold);
+        XP::add(player, guild.skill(), contract.xp)?;
+
+        if let Some(membership) = player.guilds.get(guild) {
+            membership.board.remove(index);
+            membership.contracts += 1;
+        }
+
+        Ok(Self::promote(player, guild))
     }
 }
 
@@ -74,21 +327,23 @@ impl Guilds {
 
 impl Guilds {
     fn shop() -> BTreeMap<Guild, usize> {
-        BTreeMap::from([
-            (Guild::Thieving, 10),
-            (Guild::Fishing, 100),
-            (Guild::Cooking, 200),
-            (Guild::Woodcutting, 300),
-            (Guild::Mining, 500),
-            (Guild::Smithing, 1_000),
-        ])
+        Catalog::get().guilds.clone()
     }
 
     pub fn shop_table(player: &mut Player) {
-        let mut strings: Vec<String> = vec!["Guild,Price,Member".to_string()];
+        let mut strings: Vec<String> = vec!["Guild,Price,Member,Rank,Contracts".to_string()];
 
         for (flag, price) in &Self::shop() {
-            strings.push(format!("{},{},{}", flag, price, checkmark(*player.guilds.get(flag))));
+            let membership: Option<&Membership> = player.guilds.memberships.get(flag);
+
+            strings.push(format!(
+                "{},{},{},{},{}",
+                flag,
+                thieving::markup(player, *price),
+                checkmark(membership.is_some()),
+                membership.map_or_else(|| "-".to_string(), |membership| membership.rank.to_string()),
+                membership.map_or(0, |membership| membership.contracts)
+            ));
         }
 
         csv_table(&strings);
@@ -128,21 +383,21 @@ impl Guilds {
         }
 
         if payment {
-            let gold: usize = player.bank.wallet;
-            let wallet: &mut usize = &mut player.bank.wallet;
+            let price: usize = thieving::markup(player, *price);
 
-            if gold < *price {
+            if player.bank.wallet < price {
                 return Err(Box::new(error::Inventory::NotEnoughGold));
             }
 
-            *wallet -= *price;
+            Ledger::debit(player, Source::Guilds, Account::Wallet, price);
         }
 
-        player.guilds.toggle(guild);
+        player.guilds.memberships.insert(guild, Membership::new(guild));
 
         Ok(())
     }
 
+    /// Leave a guild, giving up its rank and contracts.
     pub fn leave(player: &mut Player, guild: Guild, payment: bool) -> error::Result<()> {
         let shop: BTreeMap<Guild, usize> = Self::shop();
         let price: &usize = if let Some(item) = shop.get(&guild) {
@@ -158,14 +413,98 @@ impl Guilds {
         }
 
         if payment {
-            let wallet: &mut usize = &mut player.bank.wallet;
-            let price: usize = *price / 2;
-
-            *wallet += price;
+            Ledger::credit(player, Source::Guilds, Account::Wallet, *price / 2);
         }
 
-        player.guilds.toggle(guild);
+        player.guilds.memberships.remove(&guild);
 
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn ranks_need_level_and_contracts() {
+        use super::Rank;
+
+        assert_eq!(Rank::earned(1, 0), Rank::Apprentice);
+        assert_eq!(Rank::earned(100, 0), Rank::Apprentice);
+        assert_eq!(Rank::earned(5, 100), Rank::Journeyman);
+        assert_eq!(Rank::earned(50, 20), Rank::Grandmaster);
+        assert_eq!(Rank::Grandmaster.next(), None);
+    }
+
+    #[test]
+    fn delivering_earns_promotions() {
+        use super::{Contract, Guild, Guilds, Membership, Rank};
+        use crate::data::player::Player;
+
+        let mut player = Player::default();
+        let mut membership = Membership::new(Guild::Fishing);
+
+        membership.contracts = 1;
+        membership.board = vec![Contract {
+            item: "bait".to_string(),
+            quantity: 1,
+            gold: 10,
+            xp: 10,
+        }];
+
+        player.guilds.memberships.insert(Guild::Fishing, membership);
+        player.xp.fishing = 100_000;
+        *player.items.get("bait") += 1;
+
+        assert_eq!(
+            Guilds::deliver(&mut player, Guild::Fishing, 0).ok(),
+            Some(Some(Rank::Journeyman))
+        );
+        assert_eq!(player.guilds.memberships[&Guild::Fishing].rank, Rank::Journeyman);
+    }
+
+    #[test]
+    fn contracts_pay_no_more_than_buying_the_goods() {
+        use super::{Contract, Guild, Membership, Rank};
+        use crate::data::{
+            inventory::{
+                items,
+                market::{Listing, MIN_PRESSURE},
+            },
+            player::Player,
+        };
+        use strum::IntoEnumIterator;
+
+        let mut player = Player::default();
+
+        for guild in Guild::iter() {
+            let mut membership = Membership::new(guild);
+
+            membership.rank = Rank::Grandmaster;
+            player.guilds.memberships.insert(guild, membership);
+        }
+
+        for guild in Guild::iter() {
+            for item in guild.goods() {
+                player.market.listings.insert(
+                    item.id().to_string(),
+                    Listing {
+                        pressure: MIN_PRESSURE,
+                        history: vec![],
+                    },
+                );
+            }
+
+            for rank in Rank::iter() {
+                for _ in 0..20 {
+                    let contract = Contract::generate(guild, rank);
+                    let quote: usize = player
+                        .market
+                        .buy_quote(&contract.item, contract.quantity)
+                        .unwrap_or_default();
+
+                    assert!(contract.gold <= items::Inventory::discounted(&player, &contract.item, quote));
+                }
+            }
+        }
+    }
+}
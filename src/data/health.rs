@@ -1,16 +1,108 @@
+/*!
+# Health
+
+Hit points, hunger, and poison. Venomous enemies can poison the player,
+draining hp every round until it wears off or is cured with an antidote.
+
+Hunger grows with every fight and every shift of guild work, and only
+eating brings it back down.
+
+- Hungry (50+): Healing between rounds is halved and damage drops by a quarter
+- Starving (80+): No healing between rounds, half damage, and hp drains every round
+
+# Food
+
+- Bread: Poor quality, bought from the Trading Post or farmed from wheat
+- Cooked Fish: Good quality, the main output of the Cooking guild
+- Cooked Meat: Fine quality, cooked from the meat of hunted animals
+*/
 use serde::{Deserialize, Serialize};
+use std::fmt::Display;
+use strum_macros::EnumIter;
+
+use crate::{
+    data::{inventory::items, player::Player},
+    prelude::{error, random_num, sleep, STANDARD_SLEEP},
+};
+
+/// Highest hunger can go.
+pub const MAX_HUNGER: usize = 100;
+
+/// Hunger at which the first penalties begin.
+pub const HUNGRY: usize = 50;
+
+/// Hunger at which hp starts draining.
+pub const STARVING: usize = 80;
+
+/// Hp lost to poison every round.
+pub const POISON_DAMAGE: usize = 3;
+
+/// Rounds a poisoning lasts unless cured with an antidote.
+pub const POISON_ROUNDS: usize = 5;
+
+/// Hunger gained from each fight.
+pub const BATTLE_HUNGER: usize = 5;
+
+/// Hunger gained from each shift of guild work.
+pub const WORK_HUNGER: usize = 2;
+
+#[derive(Clone, Copy, Debug, PartialEq, EnumIter)]
+pub enum Food {
+    Bread,
+    CookedFish,
+    CookedMeat,
+}
+
+impl Display for Food {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.item())
+    }
+}
 
-use crate::prelude::{random_num, sleep, STANDARD_SLEEP};
+impl Food {
+    pub fn item(self) -> items::Types {
+        match self {
+            Food::Bread => items::Types::Food,
+            Food::CookedFish => items::Types::CookedFish,
+            Food::CookedMeat => items::Types::CookedMeat,
+        }
+    }
+
+    pub fn quality(self) -> &'static str {
+        match self {
+            Food::Bread => "Poor",
+            Food::CookedFish => "Good",
+            Food::CookedMeat => "Fine",
+        }
+    }
+
+    /// Smallest and largest amount of hunger the food satisfies.
+    pub fn restores(self) -> (usize, usize) {
+        match self {
+            Food::Bread => (5, 10),
+            Food::CookedFish => (15, 25),
+            Food::CookedMeat => (25, 40),
+        }
+    }
+}
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct Health {
     pub hp: usize,
     pub hunger: usize,
+
+    /// Rounds of battle left until the poison wears off.
+    #[serde(default)]
+    pub poison: usize,
 }
 
 impl Default for Health {
     fn default() -> Self {
-        Self { hp: 100, hunger: 0 }
+        Self {
+            hp: 100,
+            hunger: 0,
+            poison: 0,
+        }
     }
 }
 
@@ -20,21 +112,51 @@ impl Health {
     }
 
     /**
-    Only resets to default hp if less than 100 hp
-    at the end of a battle so potions aren't wasted.
+    Only resets to max hp if below it at the end
+    of a battle so potions aren't wasted. Poison
+    wears off once the battle is over.
      */
-    pub fn restore(&mut self) {
-        if self.hp < 100 {
-            self.hp = 100;
+    pub fn restore(&mut self, max_hp: usize) {
+        if self.hp < max_hp {
+            self.hp = max_hp;
         }
 
-        if self.hunger > 0 {
-            self.hunger = 0;
+        self.poison = 0;
+    }
+
+    /// Grow hunger, up to the maximum.
+    pub fn hunger_up(&mut self, amount: usize) {
+        self.hunger = (self.hunger + amount).min(MAX_HUNGER);
+    }
+
+    pub fn status(&self) -> &'static str {
+        if self.hunger >= STARVING {
+            "Starving"
+        } else if self.hunger >= HUNGRY {
+            "Hungry"
+        } else {
+            "Fed"
         }
     }
 
-    pub fn heal(&mut self) {
-        if !self.hunger == 0 || self.hp >= 100 {
+    /// Percentage of normal damage dealt at the current hunger.
+    pub fn damage_percent(&self) -> usize {
+        if self.hunger >= STARVING {
+            50
+        } else if self.hunger >= HUNGRY {
+            75
+        } else {
+            100
+        }
+    }
+
+    pub fn heal(&mut self, max_hp: usize) {
+        if self.hp >= max_hp {
+            return;
+        }
+
+        if self.hunger >= STARVING {
+            println!("You are too hungry to heal.");
             return;
         }
 
@@ -42,14 +164,86 @@ impl Health {
 
         sleep(STANDARD_SLEEP);
 
-        let new_health = random_num(1, 5);
+        let mut new_health = random_num(1, 5);
 
-        if new_health + self.hp > 100 {
-            self.hp = 100;
+        if self.hunger >= HUNGRY {
+            new_health = new_health.div_ceil(2);
+        }
+
+        if new_health + self.hp > max_hp {
+            self.hp = max_hp;
             println!("Your health is fully restored!");
         } else {
             self.hp += new_health;
             println!("Your health has been restored {new_health} points.");
         }
     }
+
+    /// Starving drains a little hp, though never the last point. Returns the hp lost.
+    pub fn starve(&mut self) -> usize {
+        if self.hunger < STARVING {
+            return 0;
+        }
+
+        let drained: usize = random_num(1, 3).min(self.hp.saturating_sub(1));
+        self.hp -= drained;
+
+        drained
+    }
+
+    /// Poison drains a little hp each round, though never the last point. Returns the hp lost.
+    pub fn poisoned(&mut self) -> usize {
+        if self.poison == 0 {
+            return 0;
+        }
+
+        self.poison -= 1;
+
+        let drained: usize = POISON_DAMAGE.min(self.hp.saturating_sub(1));
+        self.hp -= drained;
+
+        drained
+    }
+
+    /// Eat a food, returning how much hunger it satisfied.
+    pub fn eat(player: &mut Player, food: Food) -> error::Result<usize> {
+        if player.items.quantity(food.item()) == 0 {
+            return Err(Box::new(error::Inventory::NotEnoughItem(food.to_string())));
+        }
+
+        if player.health.hunger == 0 {
+            return Err(Box::new(error::Miscellaneous::Custom("You are not hungry.")));
+        }
+
+        *player.items.get(food.item()) -= 1;
+
+        let (min, max) = food.restores();
+        let satisfied: usize = random_num(min, max).min(player.health.hunger);
+
+        player.health.hunger -= satisfied;
+
+        Ok(satisfied)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn hunger_penalties() {
+        use super::{Health, MAX_HUNGER};
+
+        let mut health = Health::default();
+
+        assert_eq!(health.damage_percent(), 100);
+
+        health.hunger_up(MAX_HUNGER * 2);
+        assert_eq!(health.hunger, MAX_HUNGER);
+        assert_eq!(health.damage_percent(), 50);
+
+        health.hp = 1;
+        assert_eq!(health.starve(), 0);
+
+        health.heal(100);
+        assert_eq!(health.hp, 1);
+    }
 }
@@ -0,0 +1,144 @@
+/*!
+# Hunting
+
+Members of the Hunting guild track and trap animals for furs and meat.
+Bigger game needs a higher hunting level and is more likely to turn on the
+hunter, which starts a battle. Slaying an animal that turned still yields
+its furs and meat.
+
+# Quarry
+
+- Stag: level 1, rarely dangerous
+- Dire Wolf: level 5
+- Bear: level 10, the most furs and the most dangerous
+*/
+use crate::{
+    combat::enemy::{Enemies, Rewards},
+    data::{
+        classes,
+        guilds::Guild,
+        health::WORK_HUNGER,
+        inventory::items,
+        player::Player,
+        xp::{XPType, XP},
+    },
+    prelude::{error, random_num},
+};
+
+/// Animals that can be hunted.
+pub const QUARRY: [Enemies; 3] = [Enemies::Stag, Enemies::DireWolf, Enemies::Bear];
+
+/// How a hunt ended.
+pub enum Hunt {
+    /// The animal was trapped, yielding furs and meat.
+    Caught {
+        furs: usize,
+        meat: usize,
+    },
+    Escaped,
+
+    /// The animal turned on the hunter and must be fought.
+    Ambushed(Enemies),
+}
+
+/// Hunting level needed to track the animal.
+pub fn level(animal: Enemies) -> usize {
+    match animal {
+        Enemies::DireWolf => 5,
+        Enemies::Bear => 10,
+        _ => 1,
+    }
+}
+
+/// Percent chance the animal turns on the hunter, lowered by hunting level down to 2%.
+pub fn danger(animal: Enemies, hunting_level: usize) -> usize {
+    let base: usize = match animal {
+        Enemies::DireWolf => 20,
+        Enemies::Bear => 30,
+        _ => 5,
+    };
+
+    base.saturating_sub(hunting_level / 5).max(2)
+}
+
+/// Percent chance of trapping the animal, capped at 90%.
+pub fn success_chance(hunting_level: usize) -> usize {
+    (50 + hunting_level * 2).min(90)
+}
+
+/// Furs and meat yielded by the animal.
+pub fn yields(animal: Enemies) -> (usize, usize) {
+    match animal {
+        Enemies::DireWolf => (random_num(1, 3), random_num(1, 2)),
+        Enemies::Bear => (random_num(2, 4), random_num(2, 4)),
+        _ => (random_num(1, 2), random_num(1, 3)),
+    }
+}
+
+/// Furs and meat dropped by an animal slain after it turned on the hunter.
+pub fn battle_rewards(animal: Enemies) -> Vec<Rewards> {
+    let (furs, meat) = yields(animal);
+
+    vec![
+        Rewards::Item(items::Types::Furs.id().to_string(), furs),
+        Rewards::Item(items::Types::Meat.id().to_string(), meat),
+    ]
+}
+
+/// Go hunting for an animal. Every hunt takes an hour.
+pub fn hunt(player: &mut Player, animal: Enemies) -> error::Result<Hunt> {
+    let hunting_level: usize = XP::get_level(player.xp.hunting);
+
+    if hunting_level < level(animal) {
+        return Err(Box::new(error::Inventory::NotEnoughXP));
+    }
+
+    player.pass_time(1);
+    player.health.hunger_up(WORK_HUNGER);
+
+    if random_num(1, 100) <= danger(animal, hunting_level) {
+        XP::add(player, XPType::Hunting, level(animal))?;
+        return Ok(Hunt::Ambushed(animal));
+    }
+
+    if random_num(1, 100) > success_chance(hunting_level) {
+        XP::increment(player, XPType::Hunting);
+        return Ok(Hunt::Escaped);
+    }
+
+    let (furs, meat) = yields(animal);
+    let furs: usize = furs + classes::bonus_yield(player, XPType::Hunting);
+
+    *player.items.get(items::Types::Furs) += furs;
+    *player.items.get(items::Types::Meat) += meat;
+    player.statistics.gather(Guild::Hunting, furs + meat);
+    XP::add(player, XPType::Hunting, level(animal) * 3)?;
+
+    Ok(Hunt::Caught { furs, meat })
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn danger_falls_with_level_to_a_floor() {
+        use super::{danger, QUARRY};
+        use crate::combat::enemy::Enemies;
+
+        for animal in QUARRY {
+            assert!(danger(animal, 1) >= danger(animal, 50));
+            assert_eq!(danger(animal, 1_000), 2);
+        }
+
+        assert_eq!(danger(Enemies::Bear, 0), 30);
+    }
+
+    #[test]
+    fn success_chance_is_capped() {
+        use super::success_chance;
+
+        assert_eq!(success_chance(0), 50);
+        assert_eq!(success_chance(10), 70);
+        assert_eq!(success_chance(20), 90);
+        assert_eq!(success_chance(1_000), 90);
+    }
+}
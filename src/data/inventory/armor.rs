@@ -1,46 +1,48 @@
 use crate::{
-    data::player::Player,
+    data::{
+        catalog::{ArmorDef, Catalog},
+        inventory::{
+            bank::Account,
+            equipment::Slot,
+            ledger::{Ledger, Source},
+        },
+        player::Player,
+        thieving,
+    },
+    panic_menu,
     prelude::{checkmark, csv_table, error, select},
 };
 use rand::{thread_rng, Rng};
-use serde::{Deserialize, Serialize};
+use serde::{Deserialize, Deserializer, Serialize};
 use std::{collections::BTreeMap, fmt::Display, result::Result};
 
-#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, PartialOrd, Eq, Ord)]
-pub enum Types {
-    Leather,
-    Bronze,
-    Iron,
-    Steel,
-    Dragonhide,
-    Mystic,
-}
-
-impl Display for Types {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "{self:?} Armor")
-    }
-}
-
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
 pub struct Data {
     pub owns: bool,
     pub defense: usize,
+    #[serde(default)]
+    pub damage: usize,
     pub durability: usize,
     pub default_durability: usize,
+    #[serde(default)]
+    pub upgrade: usize,
     pub equipped: bool,
-    pub flag: Types,
+    pub slot: Slot,
+    pub name: String,
 }
 
-impl Default for Data {
-    fn default() -> Self {
+impl From<&ArmorDef> for Data {
+    fn from(armor: &ArmorDef) -> Self {
         Self {
             owns: false,
             equipped: false,
-            defense: 5,
-            durability: 50,
-            default_durability: 50,
-            flag: Types::Leather,
+            defense: armor.defense,
+            damage: armor.damage,
+            durability: armor.durability,
+            default_durability: armor.durability,
+            upgrade: 0,
+            slot: armor.slot,
+            name: armor.name.clone(),
         }
     }
 }
@@ -49,26 +51,20 @@ impl Display for Data {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(
             f,
-            "{},{},{},{},{}",
-            self.flag,
+            "{},{},{},{},+{},{},{},{}",
+            self.name,
+            self.slot,
             checkmark(self.owns),
             checkmark(self.equipped),
-            self.defense,
+            self.upgrade,
+            self.total_defense(),
+            self.total_damage(),
             self.durability,
         )
     }
 }
 
 impl Data {
-    pub fn new(defense: usize, durability: usize, flag: Types) -> Self {
-        Self {
-            defense,
-            durability,
-            flag,
-            ..Default::default()
-        }
-    }
-
     pub fn decrease_durability(&mut self) {
         let random_damage = thread_rng().gen_range(1..5);
 
@@ -79,67 +75,145 @@ impl Data {
         }
     }
 
+    /// Durability lost since the item was last bought or repaired.
+    pub fn missing_durability(&self) -> usize {
+        self.default_durability.saturating_sub(self.durability)
+    }
+
+    /// Gold needed to fully repair the item. A fully broken item costs
+    /// half of its shop price.
+    pub fn repair_cost(&self, price: usize) -> usize {
+        if self.missing_durability() == 0 {
+            return 0;
+        }
+
+        (price * self.missing_durability() / self.default_durability / 2).max(1)
+    }
+
+    pub fn repair(&mut self) {
+        self.durability = self.default_durability;
+    }
+
     pub fn break_armor(&mut self) {
-        println!("Your {} broke!", self.flag);
+        println!("Your {} broke!", self.name);
         self.owns = false;
+        self.upgrade = 0;
         self.durability = self.default_durability;
     }
+
+    /// Defense plus a 10% bonus of the base defense for each smithing upgrade level,
+    /// and never less than 1 per level so light armor still gains from upgrades.
+    /// Pieces with no defense, like rings, stay at 0.
+    pub fn total_defense(&self) -> usize {
+        if self.defense == 0 {
+            return 0;
+        }
+
+        self.defense + (self.defense * self.upgrade / 10).max(self.upgrade)
+    }
+
+    /// Bonus damage of accessories like rings and amulets, upgraded the same way as defense.
+    pub fn total_damage(&self) -> usize {
+        if self.damage == 0 {
+            return 0;
+        }
+
+        self.damage + (self.damage * self.upgrade / 10).max(self.upgrade)
+    }
 }
 
-#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
-pub struct Inventory {
-    pub leather: Data,
-    pub bronze: Data,
-    pub iron: Data,
-    pub steel: Data,
-    pub dragonhide: Data,
-    pub mystic: Data,
+/// Armor as saved in a profile. Profiles from before the catalog have no
+/// name or slot, so their stats are taken from the catalog instead.
+#[derive(Deserialize)]
+struct Saved {
+    owns: bool,
+    defense: usize,
+    #[serde(default)]
+    damage: usize,
+    durability: usize,
+    default_durability: usize,
+    #[serde(default)]
+    upgrade: usize,
+    equipped: bool,
+    slot: Option<Slot>,
+    name: Option<String>,
 }
 
-impl Default for Inventory {
-    fn default() -> Self {
-        Self {
-            leather: Data::new(10, 100, Types::Leather),
-            bronze: Data::new(30, 200, Types::Bronze),
-            iron: Data::new(50, 300, Types::Iron),
-            steel: Data::new(100, 500, Types::Steel),
-            dragonhide: Data::new(200, 500, Types::Dragonhide),
-            mystic: Data::new(1_000, 10_000, Types::Mystic),
+impl Saved {
+    fn into_data(self, id: &str) -> Data {
+        if let (None, Some(armor)) = (&self.name, Catalog::get().armor(id)) {
+            return Data {
+                owns: self.owns,
+                equipped: self.equipped,
+                durability: self.durability.min(armor.durability),
+                upgrade: self.upgrade,
+                ..Data::from(armor)
+            };
+        }
+
+        Data {
+            owns: self.owns,
+            defense: self.defense,
+            damage: self.damage,
+            durability: self.durability,
+            default_durability: self.default_durability,
+            upgrade: self.upgrade,
+            equipped: self.equipped,
+            slot: self.slot.unwrap_or(Slot::Chest),
+            name: self.name.unwrap_or_else(|| id.to_string()),
         }
     }
 }
 
+fn migrate<'de, D: Deserializer<'de>>(deserializer: D) -> Result<BTreeMap<String, Data>, D::Error> {
+    let saved: BTreeMap<String, Saved> = BTreeMap::deserialize(deserializer)?;
+
+    Ok(saved
+        .into_iter()
+        .map(|(id, armor)| {
+            let data: Data = armor.into_data(&id);
+            (id, data)
+        })
+        .collect())
+}
+
+/// Armor keyed by catalog id.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Default)]
+#[serde(transparent)]
+pub struct Inventory {
+    #[serde(deserialize_with = "migrate")]
+    pub armor: BTreeMap<String, Data>,
+}
+
 impl Inventory {
     pub fn reset(&mut self) {
         *self = Self::default();
     }
 
-    pub fn table(&self) {
-        csv_table(&[
-            "Armor,Owned,Equipped,Defense,Durability".to_string(),
-            self.leather.to_string(),
-            self.bronze.to_string(),
-            self.iron.to_string(),
-            self.steel.to_string(),
-            self.dragonhide.to_string(),
-            self.mystic.to_string(),
-        ]);
-    }
+    pub fn table(&mut self) {
+        let mut strings: Vec<String> = vec!["Armor,Slot,Owned,Equipped,Upgrade,Defense,Damage,Durability".to_string()];
 
-    pub fn get(&mut self, item_flag: &Types) -> &mut Data {
-        match item_flag {
-            Types::Bronze => &mut self.bronze,
-            Types::Dragonhide => &mut self.dragonhide,
-            Types::Iron => &mut self.iron,
-            Types::Leather => &mut self.leather,
-            Types::Mystic => &mut self.mystic,
-            Types::Steel => &mut self.steel,
+        for armor in &Catalog::get().armor {
+            strings.push(self.get(&armor.id).to_string());
         }
+
+        csv_table(&strings);
+    }
+
+    /// Get an armor piece by catalog id, adding it to the inventory if needed.
+    pub fn get(&mut self, id: &str) -> &mut Data {
+        self.armor.entry(id.to_string()).or_insert_with(|| {
+            let Some(armor) = Catalog::get().armor(id) else {
+                panic_menu!("Armor '{}' is not in the catalog.", id);
+            };
+
+            Data::from(armor)
+        })
     }
 
     /// For use in developer mode only
-    pub fn toggle_own(&mut self, item_flag: &Types) {
-        let item = self.get(item_flag);
+    pub fn toggle_own(&mut self, id: &str) {
+        let item = self.get(id);
         item.owns = !item.owns;
     }
 }
@@ -147,61 +221,57 @@ impl Inventory {
 // -------------------------------------------------- Economy -------------------------------------------------- //
 
 impl Inventory {
-    fn shop() -> BTreeMap<Types, usize> {
-        BTreeMap::from([
-            (Types::Leather, 100),
-            (Types::Bronze, 300),
-            (Types::Iron, 1_000),
-            (Types::Steel, 5_000),
-            (Types::Dragonhide, 10_000),
-            (Types::Mystic, 20_000),
-        ])
+    fn price(id: &str) -> Option<usize> {
+        Catalog::get().armor(id).map(|armor| armor.price)
     }
 
     pub fn shop_table(player: &mut Player) {
-        let mut strings: Vec<String> = vec!["Item,Price,Owns".to_string()];
-
-        for (flag, price) in &Self::shop() {
-            strings.push(format!("{},{},{}", flag, price, checkmark(player.armor.get(flag).owns)));
+        let mut strings: Vec<String> = vec!["Item,Price,Owns,Durability".to_string()];
+
+        for catalog_armor in &Catalog::get().armor {
+            let price: usize = thieving::markup(player, catalog_armor.price);
+            let armor = player.armor.get(&catalog_armor.id);
+
+            strings.push(format!(
+                "{},{},{},{}/{}",
+                armor.name,
+                price,
+                checkmark(armor.owns),
+                armor.durability,
+                armor.default_durability
+            ));
         }
 
         csv_table(&strings);
         println!("Gold: {}\n", player.bank.wallet);
     }
 
-    pub fn select() -> Types {
-        let shop = Self::shop();
-        let items: Vec<String> = shop.keys().map(std::string::ToString::to_string).collect();
+    /// Select an armor piece from the catalog, returning its id.
+    pub fn select() -> String {
+        let armor = &Catalog::get().armor;
+        let names: Vec<&String> = armor.iter().map(|armor| &armor.name).collect();
+
+        let selector = select(&names, None);
 
-        let selector = select(&items, None);
-        let selected_item = items
+        armor
             .get(selector)
             .expect("This shouldn't select a vector item out of bounds.")
-            .clone();
-
-        shop.iter()
-            .find(|item| item.0.to_string() == selected_item)
-            .map(|item| item.0)
-            .expect("Should return an Item Flag")
+            .id
             .clone()
     }
 
-    pub fn buy(player: &mut Player, flag: &Types, payment: bool) -> Result<(), error::Inventory> {
-        let shop = Self::shop();
-        let price: &usize = shop.get(flag).ok_or(error::Inventory::TransactionFailed)?;
+    pub fn buy(player: &mut Player, id: &str, payment: bool) -> Result<(), error::Inventory> {
+        let price: usize = thieving::markup(player, Self::price(id).ok_or(error::Inventory::TransactionFailed)?);
 
         if payment {
-            let gold: usize = player.bank.wallet;
-            let wallet: &mut usize = &mut player.bank.wallet;
-
-            if gold < *price {
+            if player.bank.wallet < price {
                 return Err(error::Inventory::NotEnoughGold);
             }
 
-            *wallet -= *price;
+            Ledger::debit(player, Source::ArmorShop, Account::Wallet, price);
         }
 
-        let owns_item = &mut player.armor.get(flag).owns;
+        let owns_item = &mut player.armor.get(id).owns;
 
         if *owns_item {
             return Err(error::Inventory::ItemOwned);
@@ -211,24 +281,107 @@ impl Inventory {
         Ok(())
     }
 
-    pub fn sell(player: &mut Player, flag: &Types, payment: bool) -> Result<(), error::Inventory> {
-        let shop: BTreeMap<Types, usize> = Self::shop();
-        let price: &usize = shop.get(flag).ok_or(error::Inventory::TransactionFailed)?;
-        let owns_item: &mut bool = &mut player.armor.get(flag).owns;
+    pub fn sell(player: &mut Player, id: &str, payment: bool) -> Result<(), error::Inventory> {
+        let price: usize = Self::price(id).ok_or(error::Inventory::TransactionFailed)?;
+        let item = player.armor.get(id);
 
-        if !*owns_item {
+        if !item.owns {
             return Err(error::Inventory::ItemNotOwned);
         }
 
-        *owns_item = false;
+        item.owns = false;
+        item.upgrade = 0;
+        item.repair();
 
         if payment {
-            let wallet: &mut usize = &mut player.bank.wallet;
-            let price: usize = price / 2;
+            Ledger::credit(player, Source::ArmorShop, Account::Wallet, price / 2);
+        }
 
-            *wallet += price;
+        Ok(())
+    }
+
+    /// Gold the shop charges to repair an owned item, raised by any bounty.
+    pub fn repair_price(player: &mut Player, id: &str) -> Result<usize, error::Inventory> {
+        let price: usize = Self::price(id).ok_or(error::Inventory::ItemNotExist)?;
+        let item = player.armor.get(id);
+
+        if !item.owns {
+            return Err(error::Inventory::ItemNotOwned);
         }
 
+        if item.missing_durability() == 0 {
+            return Err(error::Inventory::NothingToRepair);
+        }
+
+        let cost: usize = item.repair_cost(price);
+
+        Ok(thieving::markup(player, cost))
+    }
+
+    pub fn repair(player: &mut Player, id: &str, payment: bool) -> Result<(), error::Inventory> {
+        let price: usize = Self::repair_price(player, id)?;
+
+        if payment {
+            if player.bank.wallet < price {
+                return Err(error::Inventory::NotEnoughGold);
+            }
+
+            Ledger::debit(player, Source::Repairs, Account::Wallet, price);
+        }
+
+        player.armor.get(id).repair();
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn light_armor_gains_from_every_upgrade() {
+        use super::Data;
+        use crate::data::inventory::equipment::Slot;
+
+        let mut boots = Data {
+            owns: true,
+            defense: 2,
+            damage: 0,
+            durability: 100,
+            default_durability: 100,
+            upgrade: 0,
+            equipped: false,
+            slot: Slot::Boots,
+            name: "Leather Boots".to_string(),
+        };
+
+        for upgrade in 1..=10 {
+            let before: usize = boots.total_defense();
+
+            boots.upgrade = upgrade;
+            assert!(boots.total_defense() > before);
+        }
+    }
+
+    #[test]
+    fn accessory_damage_scales_with_upgrades() {
+        use super::Data;
+        use crate::data::inventory::equipment::Slot;
+
+        let mut ring = Data {
+            owns: true,
+            defense: 0,
+            damage: 5,
+            durability: 100,
+            default_durability: 100,
+            upgrade: 0,
+            equipped: false,
+            slot: Slot::Ring,
+            name: "Ring".to_string(),
+        };
+
+        assert_eq!(ring.total_damage(), 5);
+
+        ring.upgrade = 3;
+        assert_eq!(ring.total_damage(), 8);
+        assert_eq!(ring.total_defense(), 0);
+    }
+}
@@ -1,34 +1,96 @@
-use crate::data::player::Player;
+use crate::data::{
+    inventory::ledger::{Ledger, Source},
+    player::Player,
+};
 use crate::prelude::{
     csv_table, error, invalid_input, page_header, prompt, select, success, unreachable, Instructions,
 };
 use serde::{Deserialize, Serialize};
+use std::fmt::Display;
+
+/// Interest paid on savings each in-game day, as a percentage.
+pub const SAVINGS_INTEREST: usize = 1;
+
+/// Interest charged on loans each in-game day, as a percentage.
+pub const LOAN_INTEREST: usize = 5;
+
+/// Most gold that can be taken out of the vault each in-game day.
+pub const VAULT_DAILY_LIMIT: usize = 1_000;
 
+/// Gold the bank will lend to anyone, before counting their savings.
+pub const BASE_LOAN_LIMIT: usize = 1_000;
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
 pub enum Account {
     Wallet,
-    Account1,
-    Account2,
-    Account3,
-    Account4,
+    Checking,
+    Savings,
+    Vault,
+
+    /// Balance is the gold owed to the bank.
+    Loan,
+}
+
+impl Display for Account {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{self:?}")
+    }
+}
+
+/// The bank as saved in a profile. Profiles from before savings and the vault
+/// had four numbered accounts, which are all moved into checking.
+#[derive(Deserialize, Default)]
+#[serde(default)]
+struct Saved {
+    wallet: usize,
+    checking: usize,
+    savings: usize,
+    vault: usize,
+    vault_withdrawn: usize,
+    debt: usize,
+    account1: usize,
+    account2: usize,
+    account3: usize,
+    account4: usize,
+}
+
+impl From<Saved> for Bank {
+    fn from(saved: Saved) -> Self {
+        Self {
+            wallet: saved.wallet,
+            checking: saved.checking + saved.account1 + saved.account2 + saved.account3 + saved.account4,
+            savings: saved.savings,
+            vault: saved.vault,
+            vault_withdrawn: saved.vault_withdrawn,
+            debt: saved.debt,
+        }
+    }
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(from = "Saved")]
 pub struct Bank {
     pub wallet: usize,
-    pub account1: usize,
-    pub account2: usize,
-    pub account3: usize,
-    pub account4: usize,
+    pub checking: usize,
+    pub savings: usize,
+    pub vault: usize,
+
+    /// Gold taken out of the vault since the day began.
+    pub vault_withdrawn: usize,
+
+    /// Gold owed on the loan account, including interest.
+    pub debt: usize,
 }
 
 impl Default for Bank {
     fn default() -> Self {
         Self {
             wallet: 10,
-            account1: 0,
-            account2: 0,
-            account3: 0,
-            account4: 0,
+            checking: 0,
+            savings: 0,
+            vault: 0,
+            vault_withdrawn: 0,
+            debt: 0,
         }
     }
 }
@@ -41,67 +103,141 @@ impl Bank {
     /// Prints Bank information as a table using CSV formatting.
     pub fn table(&self) {
         csv_table(&[
-            "Account,Balance".to_string(),
-            format!("Wallet,{}", self.wallet),
-            format!("Account 1,{}", self.account1),
-            format!("Account 2,{}", self.account2),
-            format!("Account 3,{}", self.account3),
-            format!("Account 4,{}", self.account4),
+            "Account,Balance,Details".to_string(),
+            format!("Wallet,{},-", self.wallet),
+            format!("Checking,{},-", self.checking),
+            format!("Savings,{},{SAVINGS_INTEREST}% interest per day", self.savings),
+            format!("Vault,{},{} left to withdraw today", self.vault, self.vault_allowance()),
+            format!("Loan,-{},{LOAN_INTEREST}% interest per day", self.debt),
         ]);
     }
 
     pub fn balance(player: &Player, account: &Account) -> usize {
         match account {
             Account::Wallet => player.bank.wallet,
-            Account::Account1 => player.bank.account1,
-            Account::Account2 => player.bank.account2,
-            Account::Account3 => player.bank.account3,
-            Account::Account4 => player.bank.account4,
+            Account::Checking => player.bank.checking,
+            Account::Savings => player.bank.savings,
+            Account::Vault => player.bank.vault,
+            Account::Loan => player.bank.debt,
         }
     }
 
     pub fn account<'a>(player: &'a mut Player, account: &Account) -> &'a mut usize {
         match account {
-            Account::Account1 => &mut player.bank.account1,
-            Account::Account2 => &mut player.bank.account2,
-            Account::Account3 => &mut player.bank.account3,
-            Account::Account4 => &mut player.bank.account4,
+            Account::Checking => &mut player.bank.checking,
+            Account::Savings => &mut player.bank.savings,
+            Account::Vault => &mut player.bank.vault,
             Account::Wallet => &mut player.bank.wallet,
+            Account::Loan => &mut player.bank.debt,
         }
     }
 
+    /// Gold that can still be withdrawn from the vault today.
+    pub fn vault_allowance(&self) -> usize {
+        VAULT_DAILY_LIMIT.saturating_sub(self.vault_withdrawn)
+    }
+
+    /// Gold the bank is still willing to lend.
+    pub fn loan_limit(&self) -> usize {
+        (BASE_LOAN_LIMIT + (self.checking + self.savings + self.vault) / 2).saturating_sub(self.debt)
+    }
+
     pub fn deposit(player: &mut Player, account_flag: &Account, amount: usize, use_wallet: bool) -> error::Result<()> {
         let wallet_balance: usize = Self::balance(player, &Account::Wallet);
 
+        if let Account::Loan = account_flag {
+            return Err(Box::new(error::Bank::InvalidAccount));
+        }
+
         if use_wallet && wallet_balance < amount {
             return Err(Box::new(error::Inventory::NotEnoughGold));
         }
 
         if use_wallet {
-            player.bank.wallet -= amount;
+            Ledger::debit(player, Source::Deposit, Account::Wallet, amount);
         }
 
-        *Self::account(player, account_flag) += amount;
+        Ledger::credit(player, Source::Deposit, *account_flag, amount);
         Ok(())
     }
 
     pub fn withdraw(player: &mut Player, account_flag: &Account, amount: usize, use_wallet: bool) -> error::Result<()> {
         let account_balance: usize = Self::balance(player, account_flag);
 
+        if let Account::Loan = account_flag {
+            return Err(Box::new(error::Bank::InvalidAccount));
+        }
+
         if account_balance < amount {
             return Err(Box::new(error::Inventory::NotEnoughGold));
         }
 
         if use_wallet {
-            player.bank.wallet += amount;
+            if let Account::Vault = account_flag {
+                let allowance: usize = player.bank.vault_allowance();
+
+                if amount > allowance {
+                    return Err(Box::new(error::Bank::VaultLimit(allowance)));
+                }
+
+                player.bank.vault_withdrawn += amount;
+            }
+        }
+
+        Ledger::debit(player, Source::Withdrawal, *account_flag, amount);
+
+        if use_wallet {
+            Ledger::credit(player, Source::Withdrawal, Account::Wallet, amount);
+        }
+
+        Ok(())
+    }
+
+    /// Borrow gold from the bank into the wallet.
+    pub fn borrow(player: &mut Player, amount: usize) -> error::Result<()> {
+        let limit: usize = player.bank.loan_limit();
+
+        if amount > limit {
+            return Err(Box::new(error::Bank::LoanLimit(limit)));
         }
 
-        *Self::account(player, account_flag) -= amount;
+        Ledger::credit(player, Source::Loan, Account::Loan, amount);
+        Ledger::credit(player, Source::Loan, Account::Wallet, amount);
         Ok(())
     }
 
+    /// Pay back part of the loan from the wallet, returning the gold paid.
+    pub fn repay(player: &mut Player, amount: usize) -> error::Result<usize> {
+        if player.bank.debt == 0 {
+            return Err(Box::new(error::Bank::NoDebt));
+        }
+
+        let payment: usize = amount.min(player.bank.debt);
+
+        if player.bank.wallet < payment {
+            return Err(Box::new(error::Inventory::NotEnoughGold));
+        }
+
+        Ledger::debit(player, Source::Loan, Account::Wallet, payment);
+        Ledger::debit(player, Source::Loan, Account::Loan, payment);
+        Ok(payment)
+    }
+
+    /// Pay savings interest, charge loan interest, and reset the vault limit for each new day.
+    pub fn new_days(player: &mut Player, days: usize) {
+        for _ in 0..days {
+            let savings_interest: usize = player.bank.savings * SAVINGS_INTEREST / 100;
+            let loan_interest: usize = (player.bank.debt * LOAN_INTEREST).div_ceil(100);
+
+            Ledger::credit(player, Source::Interest, Account::Savings, savings_interest);
+            Ledger::credit(player, Source::Interest, Account::Loan, loan_interest);
+            player.bank.vault_withdrawn = 0;
+        }
+    }
+
+    /// Every account added together, minus any debt.
     pub fn net_worth(&self) -> usize {
-        self.account1 + self.account2 + self.account3 + self.account4 + self.wallet
+        (self.wallet + self.checking + self.savings + self.vault).saturating_sub(self.debt)
     }
 
     pub fn menu(player: &mut Player, developer_mode: bool) {
@@ -117,54 +253,36 @@ impl Bank {
             if developer_mode {
                 crate::menus::devmode::d1_developer_menu::main(player);
             } else {
-                crate::menus::game_menu::main(player);
+                crate::menus::economy::e2_the_bank::main(player);
             }
         }
 
         let account_choice = if developer_mode {
             select(
-                &[
-                    "1. Wallet",
-                    "2. Account 1",
-                    "3. Account 2",
-                    "4. Account 3",
-                    "5. Account 4",
-                    "NAV: Cancel",
-                ],
+                &["1. Wallet", "2. Checking", "3. Savings", "4. Vault", "NAV: Cancel"],
                 None,
             )
         } else {
-            select(
-                &[
-                    "1. Account 1",
-                    "2. Account 2",
-                    "3. Account 3",
-                    "4. Account 4",
-                    "NAV: Cancel",
-                ],
-                None,
-            )
+            select(&["1. Checking", "2. Savings", "3. Vault", "NAV: Cancel"], None)
         };
 
-        let mut account: Account = Account::Account1;
+        let mut account: Account = Account::Checking;
 
         if developer_mode {
             match account_choice {
                 0 => account = Account::Wallet,
-                1 => account = Account::Account1,
-                2 => account = Account::Account2,
-                3 => account = Account::Account3,
-                4 => account = Account::Account4,
-                5 => Self::menu(player, developer_mode),
+                1 => account = Account::Checking,
+                2 => account = Account::Savings,
+                3 => account = Account::Vault,
+                4 => Self::menu(player, developer_mode),
                 _ => unreachable(),
             }
         } else {
             match account_choice {
-                0 => account = Account::Account1,
-                1 => account = Account::Account2,
-                2 => account = Account::Account3,
-                3 => account = Account::Account4,
-                4 => Self::menu(player, developer_mode),
+                0 => account = Account::Checking,
+                1 => account = Account::Savings,
+                2 => account = Account::Vault,
+                3 => Self::menu(player, developer_mode),
                 _ => unreachable(),
             }
         }
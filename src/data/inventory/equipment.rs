@@ -1,17 +1,91 @@
-use serde::{Deserialize, Serialize};
+use serde::{Deserialize, Deserializer, Serialize};
+use std::fmt::Display;
+use strum::IntoEnumIterator;
+use strum_macros::EnumIter;
 
 use crate::panic_menu;
 
-use crate::prelude::{page_header, pause, select, unreachable, Instructions};
+use crate::prelude::{csv_table, page_header, pause, select, unreachable, Instructions};
 
-use crate::data::player::Player;
+use crate::data::{catalog::Catalog, player::Player};
 
-use super::{armor, weapons};
+use super::loot::{self, Kind};
+
+/// Places on the body where armor and accessories can be worn.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, PartialOrd, Eq, Ord, EnumIter)]
+pub enum Slot {
+    Helmet,
+    Chest,
+    Legs,
+    Boots,
+    Gloves,
+    Shield,
+    Ring,
+    Amulet,
+}
+
+impl Display for Slot {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{self:?}")
+    }
+}
+
+/// Profiles from before the catalog saved the equipped weapon by type, like `Iron` or `WizardStaff`.
+fn weapon_id<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Option<String>, D::Error> {
+    let id: Option<String> = Option::deserialize(deserializer)?;
+
+    Ok(id.map(|id| match id.as_str() {
+        "WizardStaff" => "wizard_staff".to_string(),
+        "Wooden" | "Bronze" | "Iron" | "Steel" | "Mystic" => format!("{}_sword", id.to_lowercase()),
+        _ => id,
+    }))
+}
+
+/// Profiles from before the catalog saved equipped armor by type, like `Leather` or `RubyRing`.
+fn armor_id<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Option<String>, D::Error> {
+    let id: Option<String> = Option::deserialize(deserializer)?;
+
+    Ok(id.map(|id| {
+        if !id.starts_with(char::is_uppercase) {
+            return id;
+        }
+
+        let mut snake_case = String::new();
+
+        for (index, c) in id.chars().enumerate() {
+            if c.is_uppercase() && index > 0 {
+                snake_case.push('_');
+            }
+
+            snake_case.push(c.to_ascii_lowercase());
+        }
+
+        snake_case
+    }))
+}
 
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Default)]
 pub struct Equipment {
-    pub armor: Option<armor::Types>,
-    pub weapon: Option<weapons::Types>,
+    #[serde(default, deserialize_with = "weapon_id")]
+    pub weapon: Option<String>,
+    #[serde(default, deserialize_with = "armor_id")]
+    pub helmet: Option<String>,
+
+    /// Saved as `armor` before there were other armor slots.
+    #[serde(default, alias = "armor", deserialize_with = "armor_id")]
+    pub chest: Option<String>,
+    #[serde(default, deserialize_with = "armor_id")]
+    pub legs: Option<String>,
+    #[serde(default, deserialize_with = "armor_id")]
+    pub boots: Option<String>,
+    #[serde(default, deserialize_with = "armor_id")]
+    pub gloves: Option<String>,
+    #[serde(default, deserialize_with = "armor_id")]
+    pub shield: Option<String>,
+    #[serde(default, deserialize_with = "armor_id")]
+    pub ring: Option<String>,
+    #[serde(default, deserialize_with = "armor_id")]
+    pub amulet: Option<String>,
 }
 
 impl Equipment {
@@ -19,10 +93,141 @@ impl Equipment {
         *self = Self::default();
     }
 
+    pub fn get(&mut self, slot: Slot) -> &mut Option<String> {
+        match slot {
+            Slot::Helmet => &mut self.helmet,
+            Slot::Chest => &mut self.chest,
+            Slot::Legs => &mut self.legs,
+            Slot::Boots => &mut self.boots,
+            Slot::Gloves => &mut self.gloves,
+            Slot::Shield => &mut self.shield,
+            Slot::Ring => &mut self.ring,
+            Slot::Amulet => &mut self.amulet,
+        }
+    }
+
+    /// Every armor piece currently worn.
+    pub fn worn_armor(&self) -> Vec<String> {
+        [
+            &self.helmet,
+            &self.chest,
+            &self.legs,
+            &self.boots,
+            &self.gloves,
+            &self.shield,
+            &self.ring,
+            &self.amulet,
+        ]
+        .into_iter()
+        .flatten()
+        .cloned()
+        .collect()
+    }
+
+    /// Prints every slot and what is equipped in it, shop gear or loot.
+    pub fn table(&self, loot: &loot::Inventory) {
+        fn entry<T: Display>(slot: &str, item: Option<T>, kind: Kind, loot: &loot::Inventory) -> String {
+            if let Some(item) = item {
+                return format!("{slot},{item}");
+            }
+
+            match loot.equipped().find(|gear| gear.kind == kind) {
+                Some(gear) => format!("{slot},{gear}"),
+                None => format!("{slot},-"),
+            }
+        }
+
+        let catalog = Catalog::get();
+        let mut equipment = self.clone();
+        let weapon = self
+            .weapon
+            .as_deref()
+            .and_then(|id| catalog.weapon(id))
+            .map(|weapon| &weapon.name);
+        let mut strings: Vec<String> = vec!["Slot,Equipped".to_string(), entry("Weapon", weapon, Kind::Weapon, loot)];
+
+        for slot in Slot::iter() {
+            let armor = equipment
+                .get(slot)
+                .as_deref()
+                .and_then(|id| catalog.armor(id))
+                .map(|armor| &armor.name);
+
+            strings.push(entry(&slot.to_string(), armor, Kind::Armor(slot), loot));
+        }
+
+        csv_table(&strings);
+    }
+
+    /// Whether a shop weapon or a loot weapon is equipped.
+    pub fn has_weapon(player: &Player) -> bool {
+        player.equipment.weapon.is_some() || player.loot.equipped().any(|gear| gear.kind == Kind::Weapon)
+    }
+
+    /// Whether anything is worn in the given slot, shop armor or loot.
+    pub fn has_armor(player: &Player, slot: Slot) -> bool {
+        let mut equipment = player.equipment.clone();
+
+        equipment.get(slot).is_some() || player.loot.equipped().any(|gear| gear.kind == Kind::Armor(slot))
+    }
+
+    /// Combined defense of every armor piece worn.
+    pub fn defense(player: &mut Player) -> usize {
+        player
+            .equipment
+            .worn_armor()
+            .iter()
+            .map(|id| player.armor.get(id).total_defense())
+            .sum::<usize>()
+            + player.loot.defense()
+    }
+
+    /// Extra damage granted by worn accessories.
+    pub fn bonus_damage(player: &mut Player) -> usize {
+        player
+            .equipment
+            .worn_armor()
+            .iter()
+            .map(|id| player.armor.get(id).total_damage())
+            .sum::<usize>()
+            + player.loot.bonus_damage()
+    }
+
+    /// Every worn armor piece loses durability, and broken pieces are unequipped.
+    pub fn wear_armor(player: &mut Player) {
+        for id in player.equipment.worn_armor() {
+            let armor = player.armor.get(&id);
+
+            armor.decrease_durability();
+
+            if !armor.owns {
+                let slot: Slot = armor.slot;
+                Self::unequip_armor(player, slot, false);
+            }
+        }
+
+        let worn_loot: Vec<usize> = player
+            .loot
+            .equipped()
+            .filter(|gear| gear.kind != Kind::Weapon)
+            .map(|gear| gear.id)
+            .collect();
+
+        for id in worn_loot {
+            if let Some(gear) = player.loot.get(id) {
+                if gear.decrease_durability() {
+                    player.loot.remove(id);
+                }
+            }
+        }
+    }
+
     pub fn menu(player: &mut Player) {
         page_header("Equipment Manager", &Instructions::Keyboard);
 
-        let choice = select(&["1. Weapons", "2. Armor", "NAV: Go Back"], None);
+        player.equipment.table(&player.loot);
+
+        let choice = select(&["1. Weapons", "2. Armor", "3. Loot", "NAV: Go Back"], None);
 
         match choice {
             0 => {
@@ -33,7 +238,11 @@ impl Equipment {
                 Self::armor_menu(player);
                 Self::menu(player);
             }
-            2 => player.save(), // goes back to whatever menu called it due to recursion
+            2 => {
+                Self::loot_menu(player);
+                Self::menu(player);
+            }
+            3 => player.save(), // goes back to whatever menu called it due to recursion
             _ => unreachable(),
         }
     }
@@ -51,7 +260,10 @@ impl Equipment {
                 Self::armor_menu(player);
             }
             1 => {
-                Self::unequip_armor(player, true);
+                let slots: Vec<Slot> = Slot::iter().collect();
+                let slot: usize = select(&slots, Some("Select a slot to un-equip"));
+
+                Self::unequip_armor(player, slots[slot], true);
                 Self::armor_menu(player);
             }
             2 => {}
@@ -81,30 +293,16 @@ impl Equipment {
     }
 
     pub fn equip_weapon(player: &mut Player) {
-        use weapons::Types as W;
-
-        let choices = [
-            player.weapons.wooden_sword.flag.to_string(),
-            player.weapons.bronze_sword.flag.to_string(),
-            player.weapons.iron_sword.flag.to_string(),
-            player.weapons.steel_sword.flag.to_string(),
-            player.weapons.mystic_sword.flag.to_string(),
-            player.weapons.wizard_staff.flag.to_string(),
-        ];
+        let weapons = &Catalog::get().weapons;
+        let choices: Vec<&String> = weapons.iter().map(|weapon| &weapon.name).collect();
 
         let choice: usize = select(&choices, None);
 
-        let flag = match choice {
-            0 => W::Wooden,
-            1 => W::Bronze,
-            2 => W::Iron,
-            3 => W::Steel,
-            4 => W::Mystic,
-            5 => W::WizardStaff,
-            _ => panic_menu!("Out of bounds"),
+        let Some(id) = weapons.get(choice).map(|weapon| weapon.id.clone()) else {
+            panic_menu!("Out of bounds");
         };
 
-        let weapon = player.weapons.get(&flag);
+        let weapon = player.weapons.get(&id);
 
         if !weapon.owns {
             println!("You do not own this.");
@@ -114,10 +312,12 @@ impl Equipment {
 
         weapon.equipped = true;
 
-        println!("Equipped the {}", weapon.flag);
+        println!("Equipped the {}", weapon.name);
 
         Self::unequip_weapon(player, false);
-        player.equipment.weapon = Some(flag);
+        Self::unequip_loot_kind(player, Kind::Weapon);
+        player.weapons.get(&id).equipped = true;
+        player.equipment.weapon = Some(id);
 
         pause();
     }
@@ -129,8 +329,8 @@ impl Equipment {
             return;
         }
 
-        if let Some(equipped_weapon) = &player.equipment.weapon {
-            let equipped_weapon = player.weapons.get(equipped_weapon);
+        if let Some(equipped_weapon) = player.equipment.weapon.clone() {
+            let equipped_weapon = player.weapons.get(&equipped_weapon);
 
             equipped_weapon.equipped = false;
 
@@ -143,31 +343,22 @@ impl Equipment {
         }
     }
 
+    /// Equip any owned armor piece into its slot, replacing what was there.
     pub fn equip_armor(player: &mut Player) {
-        use armor::Types as A;
-
-        let choices = [
-            player.armor.leather.flag.to_string(),
-            player.armor.bronze.flag.to_string(),
-            player.armor.iron.flag.to_string(),
-            player.armor.steel.flag.to_string(),
-            player.armor.dragonhide.flag.to_string(),
-            player.armor.mystic.flag.to_string(),
-        ];
+        let catalog_armor = &Catalog::get().armor;
+        let choices: Vec<String> = catalog_armor
+            .iter()
+            .map(|armor| format!("{} ({})", armor.name, armor.slot))
+            .collect();
 
         let choice: usize = select(&choices, None);
 
-        let flag = match choice {
-            0 => A::Leather,
-            1 => A::Bronze,
-            2 => A::Iron,
-            3 => A::Steel,
-            4 => A::Dragonhide,
-            5 => A::Mystic,
-            _ => panic_menu!("Out of bounds"),
+        let Some(armor) = catalog_armor.get(choice) else {
+            panic_menu!("Out of bounds");
         };
 
-        let armor = player.armor.get(&flag);
+        let (id, slot): (String, Slot) = (armor.id.clone(), armor.slot);
+        let armor = player.armor.get(&id);
 
         if !armor.owns {
             println!("You do not own this.");
@@ -175,51 +366,130 @@ impl Equipment {
             return;
         }
 
-        armor.equipped = true;
+        println!("Equipped the {}", armor.name);
 
-        println!("Equipped the {}", armor.flag);
-
-        Self::unequip_armor(player, false);
-        player.equipment.armor = Some(flag);
+        Self::unequip_armor(player, slot, false);
+        Self::unequip_loot_kind(player, Kind::Armor(slot));
+        player.armor.get(&id).equipped = true;
+        *player.equipment.get(slot) = Some(id);
 
         pause();
     }
 
-    pub fn unequip_armor(player: &mut Player, menu_facing: bool) {
-        if player.equipment.armor.is_none() && menu_facing {
-            println!("You do not have armor equipped.");
+    pub fn unequip_armor(player: &mut Player, slot: Slot, menu_facing: bool) {
+        if player.equipment.get(slot).is_none() && menu_facing {
+            println!("You do not have anything equipped in the {slot} slot.");
             pause();
             return;
         }
 
-        if let Some(equipped_armor) = &player.equipment.armor {
-            let equipped_armor = player.armor.get(equipped_armor);
-
-            equipped_armor.equipped = false;
+        if let Some(equipped_armor) = player.equipment.get(slot).take() {
+            let armor = player.armor.get(&equipped_armor);
 
-            player.equipment.armor = None;
+            armor.equipped = false;
 
             if menu_facing {
-                println!("Armor successfully unequipped.");
+                println!("{} successfully unequipped.", armor.name);
                 pause();
             }
         }
     }
 
+    pub fn loot_menu(player: &mut Player) {
+        page_header("Equipment Manager - Loot", &Instructions::Keyboard);
+
+        player.loot.table();
+
+        let choices: usize = select(
+            &["1. Equip Loot", "2. Un-Equip Loot", "3. Salvage Loot", "NAV: Go Back"],
+            None,
+        );
+
+        if choices == 3 {
+            return;
+        }
+
+        let Some(id) = Self::select_loot(player) else {
+            println!("You do not have any loot.");
+            pause();
+            return;
+        };
+
+        match choices {
+            0 => Self::equip_loot(player, id),
+            1 => {
+                if let Some(gear) = player.loot.get(id) {
+                    gear.equipped = false;
+                }
+            }
+            2 => match loot::Inventory::salvage(player, id) {
+                Ok(gold) => {
+                    println!("Salvaged for {gold} gold.");
+                    pause();
+                }
+                Err(message) => message.print(true),
+            },
+            _ => unreachable(),
+        }
+
+        Self::loot_menu(player);
+    }
+
+    fn select_loot(player: &Player) -> Option<usize> {
+        if player.loot.gear.is_empty() {
+            return None;
+        }
+
+        let names: Vec<String> = player.loot.gear.iter().map(ToString::to_string).collect();
+        let choice: usize = select(&names, None);
+
+        player.loot.gear.get(choice).map(|gear| gear.id)
+    }
+
+    /// Equip a piece of loot, replacing anything in the same slot.
+    pub fn equip_loot(player: &mut Player, id: usize) {
+        let Some(kind) = player.loot.get(id).map(|gear| gear.kind) else {
+            return;
+        };
+
+        match kind {
+            Kind::Weapon => Self::unequip_weapon(player, false),
+            Kind::Armor(slot) => Self::unequip_armor(player, slot, false),
+        }
+
+        Self::unequip_loot_kind(player, kind);
+
+        if let Some(gear) = player.loot.get(id) {
+            gear.equipped = true;
+            println!("Equipped the {}", gear.name);
+            pause();
+        }
+    }
+
+    fn unequip_loot_kind(player: &mut Player, kind: Kind) {
+        for gear in &mut player.loot.gear {
+            if gear.kind == kind {
+                gear.equipped = false;
+            }
+        }
+    }
+
+    /// Un-equip every slot holding something the player no longer owns.
     pub fn check_equipment_ownership(player: &mut Player) {
-        if let Some(weapon_flag) = &player.equipment.weapon {
-            let weapon = player.weapons.get(weapon_flag);
+        if let Some(weapon_id) = player.equipment.weapon.clone() {
+            let weapon = player.weapons.get(&weapon_id);
 
             if !weapon.owns {
                 Self::unequip_weapon(player, false);
             }
         }
 
-        if let Some(armor_flag) = &player.equipment.armor {
-            let armor = player.armor.get(armor_flag);
+        for id in player.equipment.worn_armor() {
+            let armor = player.armor.get(&id);
 
             if !armor.owns {
-                Self::unequip_armor(player, false);
+                let slot: Slot = armor.slot;
+                Self::unequip_armor(player, slot, false);
             }
         }
     }
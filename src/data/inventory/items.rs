@@ -1,15 +1,24 @@
 use serde::{Deserialize, Serialize};
-use std::hash::Hash;
-use std::{collections::BTreeMap, fmt::Display};
-
-use crate::data::player::Player;
+use std::collections::BTreeMap;
+use std::fmt::Display;
+use strum_macros::EnumIter;
+
+use crate::data::{
+    catalog::Catalog,
+    inventory::{
+        bank::Account,
+        ledger::{Ledger, Source},
+        weight,
+    },
+    player::Player,
+    thieving,
+};
 use crate::prelude::{csv_table, error, generic_prompt, select};
 use std::result::Result;
 
-type ShopItem = (Types, usize, usize);
-type Pair = (ShopItem, ShopItem);
-
-#[derive(Hash, PartialEq, Eq, PartialOrd, Ord, Clone, Copy)]
+/// Core items that game mechanics depend on. Every other item only needs
+/// an entry in the catalog.
+#[derive(Hash, PartialEq, Eq, PartialOrd, Ord, Clone, Copy, Debug, EnumIter)]
 pub enum Types {
     Bait,
     Seeds,
@@ -25,74 +34,65 @@ pub enum Types {
     Bones,
     DragonHides,
     RunicTablets,
+    StrongPotions,
+    Herbs,
+    Meat,
+    CookedFish,
+    CookedMeat,
+    StrengthPotions,
+    DefensePotions,
+    Antidotes,
+    XPBoostPotions,
 }
 
 impl Display for Types {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(
-            f,
-            "{}",
-            match self {
-                Types::Bait => "Bait",
-                Types::Seeds => "Seeds",
-                Types::Furs => "Fur",
-                Types::Fish => "Fish",
-                Types::Food => "Food",
-                Types::Wood => "Wood",
-                Types::Ore => "Ore",
-                Types::Ingots => "Ingot",
-                Types::Potions => "Potion",
-                Types::Rubies => "Ruby",
-                Types::MagicScrolls => "Magic Scroll",
-                Types::Bones => "Bone",
-                Types::DragonHides => "Dragon Hide",
-                Types::RunicTablets => "Runic Tablet",
-            }
-        )
+        write!(f, "{}", Catalog::get().item_name(self.id()))
     }
 }
 
-#[derive(Hash, PartialEq, Eq, PartialOrd, Ord, Clone, Copy)]
-pub enum GuildTypes {
-    Bait,
-    Fish,
-    Food,
-    Wood,
-    Ore,
-    Ingots,
-    Gold,
+impl AsRef<str> for Types {
+    fn as_ref(&self) -> &str {
+        self.id()
+    }
 }
 
-impl GuildTypes {
-    pub fn to_mundane_item(&self) -> Option<Types> {
+impl Types {
+    /// Catalog id of the item.
+    pub fn id(&self) -> &'static str {
         match self {
-            GuildTypes::Ore => Some(Types::Ore),
-            GuildTypes::Bait => Some(Types::Bait),
-            GuildTypes::Fish => Some(Types::Fish),
-            GuildTypes::Food => Some(Types::Food),
-            GuildTypes::Ingots => Some(Types::Ingots),
-            GuildTypes::Wood => Some(Types::Wood),
-            GuildTypes::Gold => None,
+            Types::Bait => "bait",
+            Types::Seeds => "seeds",
+            Types::Furs => "furs",
+            Types::Fish => "fish",
+            Types::Food => "food",
+            Types::Wood => "wood",
+            Types::Ore => "ore",
+            Types::Ingots => "ingots",
+            Types::Potions => "potions",
+            Types::Rubies => "rubies",
+            Types::MagicScrolls => "magic_scrolls",
+            Types::Bones => "bones",
+            Types::DragonHides => "dragon_hides",
+            Types::RunicTablets => "runic_tablets",
+            Types::StrongPotions => "strong_potions",
+            Types::Herbs => "herbs",
+            Types::Meat => "meat",
+            Types::CookedFish => "cooked_fish",
+            Types::CookedMeat => "cooked_meat",
+            Types::StrengthPotions => "strength_potions",
+            Types::DefensePotions => "defense_potions",
+            Types::Antidotes => "antidotes",
+            Types::XPBoostPotions => "xp_boost_potions",
         }
     }
 }
 
+/// Item quantities keyed by catalog id.
 #[derive(Serialize, Deserialize, Debug, Clone, Default)]
+#[serde(transparent)]
 pub struct Inventory {
-    pub bait: usize,
-    pub seeds: usize,
-    pub furs: usize,
-    pub fish: usize,
-    pub food: usize,
-    pub wood: usize,
-    pub ore: usize,
-    pub ingots: usize,
-    pub potions: usize,
-    pub rubies: usize,
-    pub magic_scrolls: usize,
-    pub bones: usize,
-    pub dragon_hides: usize,
-    pub runic_tablets: usize,
+    pub quantities: BTreeMap<String, usize>,
 }
 
 impl Inventory {
@@ -100,79 +100,48 @@ impl Inventory {
         *self = Self::default();
     }
 
-    pub fn get(&mut self, flag: Types) -> &mut usize {
-        match flag {
-            Types::Bait => &mut self.bait,
-            Types::Bones => &mut self.bones,
-            Types::DragonHides => &mut self.dragon_hides,
-            Types::Fish => &mut self.fish,
-            Types::Food => &mut self.food,
-            Types::Furs => &mut self.furs,
-            Types::Ingots => &mut self.ingots,
-            Types::MagicScrolls => &mut self.magic_scrolls,
-            Types::Ore => &mut self.ore,
-            Types::Potions => &mut self.potions,
-            Types::Rubies => &mut self.rubies,
-            Types::RunicTablets => &mut self.runic_tablets,
-            Types::Seeds => &mut self.seeds,
-            Types::Wood => &mut self.wood,
-        }
+    pub fn get<T: AsRef<str>>(&mut self, id: T) -> &mut usize {
+        self.quantities.entry(id.as_ref().to_string()).or_insert(0)
+    }
+
+    /// Quantity of an item without needing mutable access.
+    pub fn quantity<T: AsRef<str>>(&self, id: T) -> usize {
+        self.quantities.get(id.as_ref()).copied().unwrap_or(0)
     }
 }
 
 // -------------------------------------------------- Economy -------------------------------------------------- //
 
 impl Inventory {
-    fn shop() -> BTreeMap<Types, usize> {
-        BTreeMap::from([
-            (Types::Bait, 1),
-            (Types::Seeds, 1),
-            (Types::Furs, 50),
-            (Types::Fish, 5),
-            (Types::Food, 10),
-            (Types::Wood, 10),
-            (Types::Ore, 15),
-            (Types::Ingots, 30),
-            (Types::Potions, 20),
-            (Types::Rubies, 100),
-            (Types::MagicScrolls, 200),
-            (Types::Bones, 10),
-            (Types::DragonHides, 50),
-            (Types::RunicTablets, 300),
-        ])
-    }
-
     pub fn shop_table(player: &mut Player) {
-        let mut strings: Vec<String> = vec!["Item,Price,Quantity,,Item,Price,Quantity".to_string()];
-
-        let mut pairs: Vec<Pair> = vec![];
-        let mut current_pair: Pair = ((Types::Bait, 0, 0), (Types::Bait, 0, 0));
-        let mut index: usize = 0;
-
-        for (flag, usize) in &Self::shop() {
-            let quantity = player.items.get(*flag);
-
-            if index == 0 {
-                current_pair.0 = (*flag, *usize, *quantity);
-                index += 1;
-            } else if index == 1 {
-                current_pair.1 = (*flag, *usize, *quantity);
-                pairs.push(current_pair);
-                index = 0;
-            }
-        }
+        let mut strings: Vec<String> = vec!["Item,Buy,Sell,Quantity,History".to_string()];
+
+        for item in &Catalog::get().items {
+            let history: Vec<String> = player
+                .market
+                .history(&item.id)
+                .iter()
+                .map(ToString::to_string)
+                .collect();
 
-        for (item1, item2) in &pairs {
             strings.push(format!(
-                "{},{},{},,{},{},{}",
-                item1.0, item1.1, item1.2, item2.0, item2.1, item2.2
+                "{},{},{},{},{}",
+                item.name,
+                Self::discounted(
+                    player,
+                    &item.id,
+                    player.market.buy_quote(&item.id, 1).unwrap_or(item.price)
+                ),
+                player.market.sell_quote(&item.id, 1).unwrap_or(item.price / 2),
+                player.items.quantity(&item.id),
+                history.join(" ")
             ));
         }
 
         csv_table(&strings);
     }
 
-    pub fn build_transaction() -> Result<(Types, usize), error::Inventory> {
+    pub fn build_transaction() -> Result<(String, usize), error::Inventory> {
         let item = Self::select();
 
         match generic_prompt::<usize>("Quantity:") {
@@ -181,62 +150,69 @@ impl Inventory {
         }
     }
 
-    pub fn select() -> Types {
-        let shop = Self::shop();
-        let items = shop.keys();
-        let item_names: Vec<String> = items.map(std::string::ToString::to_string).collect();
+    /// Select an item from the catalog, returning its id.
+    pub fn select() -> String {
+        let catalog = Catalog::get();
+        let item_names: Vec<&String> = catalog.items.iter().map(|item| &item.name).collect();
 
         let selector = select(&item_names, None);
-        let selected_item = item_names
+
+        catalog
+            .items
             .get(selector)
             .expect("This shouldn't select a vector item out of bounds.")
-            .clone();
+            .id
+            .clone()
+    }
 
-        *Self::shop()
-            .iter()
-            .find(|item| item.0.to_string() == selected_item)
-            .map(|item| item.0)
-            .expect("Should return an Item Flag")
+    /// Price after the discount from the player's best guild rank for the item, raised by any bounty.
+    pub fn discounted(player: &Player, id: &str, price: usize) -> usize {
+        thieving::markup(player, price - price * player.guilds.item_discount(id) / 100)
     }
 
-    pub fn buy(player: &mut Player, flag: Types, quantity: usize, use_wallet: bool) -> Result<(), error::Inventory> {
-        let shop = Self::shop();
-        let usize: usize = *shop.get(&flag).ok_or(error::Inventory::TransactionFailed)?;
+    /// Buy items at the market price, less any guild rank discount and plus any bounty markup.
+    /// Purchases paid from the wallet move the market and must fit in the player's carry capacity.
+    pub fn buy(player: &mut Player, id: &str, quantity: usize, use_wallet: bool) -> Result<(), error::Inventory> {
+        let quote: usize = player
+            .market
+            .buy_quote(id, quantity)
+            .ok_or(error::Inventory::TransactionFailed)?;
+        let price: usize = Self::discounted(player, id, quote);
 
         if use_wallet {
-            let gold: usize = player.bank.wallet;
-            let wallet: &mut usize = &mut player.bank.wallet;
-            let usize = quantity * usize;
+            weight::check_room(player, id, quantity)?;
 
-            if gold < usize {
+            if player.bank.wallet < price {
                 return Err(error::Inventory::NotEnoughGold);
             }
 
-            *wallet -= usize;
+            Ledger::debit(player, Source::TradingPost, Account::Wallet, price);
+            player.market.record_buy(id, quantity);
         }
 
-        let item = player.items.get(flag);
+        let item = player.items.get(id);
 
         *item += quantity;
         Ok(())
     }
 
-    pub fn sell(player: &mut Player, flag: Types, quantity: usize, use_wallet: bool) -> Result<(), error::Inventory> {
-        let shop = Self::shop();
-        let usize: usize = *shop.get(&flag).ok_or(error::Inventory::ItemNotExist)?;
-        let item = player.items.get(flag);
+    /// Sell items at half of the market price. Only sales paid to the wallet move the market.
+    pub fn sell(player: &mut Player, id: &str, quantity: usize, use_wallet: bool) -> Result<(), error::Inventory> {
+        let price: usize = player
+            .market
+            .sell_quote(id, quantity)
+            .ok_or(error::Inventory::ItemNotExist)?;
+        let item = player.items.get(id);
 
         if *item == 0 || *item < quantity {
-            return Err(error::Inventory::NotEnoughItem(flag.to_string()));
+            return Err(error::Inventory::NotEnoughItem(Catalog::get().item_name(id)));
         }
 
         *item -= quantity;
 
         if use_wallet {
-            let wallet: &mut usize = &mut player.bank.wallet;
-            let usize: usize = quantity * (usize / 2);
-
-            *wallet += usize;
+            Ledger::credit(player, Source::TradingPost, Account::Wallet, price);
+            player.market.record_sell(id, quantity);
         }
 
         Ok(())
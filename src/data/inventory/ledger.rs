@@ -0,0 +1,146 @@
+/*!
+# Ledger
+
+Every change to the wallet and bank accounts is written to the ledger with
+the in-game time, where the gold came from or went, and the balance left
+afterward. Only the most recent entries are kept.
+*/
+use crate::{
+    data::{
+        clock::Clock,
+        inventory::bank::{Account, Bank},
+        player::Player,
+    },
+    prelude::csv_table,
+};
+use serde::{Deserialize, Serialize};
+use std::fmt::Display;
+use strum_macros::EnumIter;
+
+/// Most entries kept in the ledger before the oldest are dropped.
+pub const MAX_ENTRIES: usize = 500;
+
+/// Where gold came from or went to.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, EnumIter)]
+pub enum Source {
+    Deposit,
+    Withdrawal,
+    Loan,
+    Interest,
+    Storage,
+    TradingPost,
+    WeaponsShop,
+    ArmorShop,
+    Repairs,
+    Guilds,
+    Thieving,
+    Fines,
+    Battle,
+    Salvage,
+    Death,
+    Achievements,
+    Quests,
+}
+
+impl Display for Source {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::TradingPost => write!(f, "Trading Post"),
+            Self::WeaponsShop => write!(f, "Weapons Shop"),
+            Self::ArmorShop => write!(f, "Armor Shop"),
+            other => write!(f, "{other:?}"),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct Entry {
+    /// In-game hour the change happened.
+    pub hours: usize,
+    pub source: Source,
+    pub account: Account,
+    pub amount: isize,
+    pub balance: usize,
+}
+
+impl Display for Entry {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{},{},{},{:+},{}",
+            Clock { hours: self.hours },
+            self.source,
+            self.account,
+            self.amount,
+            self.balance
+        )
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
+#[serde(transparent)]
+pub struct Ledger {
+    pub entries: Vec<Entry>,
+}
+
+impl Ledger {
+    pub fn record(&mut self, entry: Entry) {
+        self.entries.push(entry);
+
+        if self.entries.len() > MAX_ENTRIES {
+            self.entries.remove(0);
+        }
+    }
+
+    /// Prints the most recent entries first, optionally only those from one source.
+    pub fn table(&self, source: Option<Source>, limit: usize) {
+        let mut strings: Vec<String> = vec!["Time,Source,Account,Amount,Balance".to_string()];
+
+        strings.extend(
+            self.entries
+                .iter()
+                .rev()
+                .filter(|entry| source.is_none() || source == Some(entry.source))
+                .take(limit)
+                .map(ToString::to_string),
+        );
+
+        csv_table(&strings);
+    }
+
+    fn log(player: &mut Player, source: Source, account: Account, amount: isize) {
+        player.statistics.record_gold(source, account, amount);
+
+        let entry = Entry {
+            hours: player.clock.hours,
+            source,
+            account,
+            amount,
+            balance: Bank::balance(player, &account),
+        };
+
+        player.ledger.record(entry);
+    }
+
+    /// Add gold to an account and record it.
+    pub fn credit(player: &mut Player, source: Source, account: Account, gold: usize) {
+        if gold == 0 {
+            return;
+        }
+
+        *Bank::account(player, &account) += gold;
+        Self::log(player, source, account, isize::try_from(gold).unwrap_or(isize::MAX));
+    }
+
+    /// Take gold out of an account and record it. Callers check the balance first.
+    pub fn debit(player: &mut Player, source: Source, account: Account, gold: usize) {
+        if gold == 0 {
+            return;
+        }
+
+        let balance: &mut usize = Bank::account(player, &account);
+        *balance = balance.saturating_sub(gold);
+
+        Self::log(player, source, account, -isize::try_from(gold).unwrap_or(isize::MAX));
+    }
+}
@@ -0,0 +1,376 @@
+/*!
+# Loot
+
+Randomly generated gear dropped by enemies. Unlike the fixed weapons and
+armor sold in the shops, every piece of loot is its own instance with a
+rarity tier and a set of random affixes.
+
+# Rarity
+
+| Rarity    | Stat Multiplier | Affixes |
+|-----------|-----------------|---------|
+| Common    | 1x              | 0       |
+| Uncommon  | 2x              | 1       |
+| Rare      | 3x              | 2       |
+| Epic      | 5x              | 3       |
+| Legendary | 8x              | 4       |
+
+Legendary drops are always one of the named unique items.
+*/
+use crate::{
+    data::{
+        inventory::{
+            bank::Account,
+            equipment::Slot,
+            ledger::{Ledger, Source},
+        },
+        player::Player,
+    },
+    prelude::{checkmark, csv_table, error, random_num},
+};
+use serde::{Deserialize, Serialize};
+use std::fmt::Display;
+use strum::IntoEnumIterator;
+use strum_macros::EnumIter;
+
+/// Named legendary items and the slot they are worn in.
+const UNIQUES: [(&str, Kind); 6] = [
+    ("Dawnbreaker", Kind::Weapon),
+    ("Wyrmfang", Kind::Weapon),
+    ("Crown of the Lich King", Kind::Armor(Slot::Helmet)),
+    ("Aegis of Albion", Kind::Armor(Slot::Shield)),
+    ("Band of Endless Greed", Kind::Armor(Slot::Ring)),
+    ("Heart of the Stronghold", Kind::Armor(Slot::Amulet)),
+];
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, PartialOrd, Eq, Ord, EnumIter)]
+pub enum Rarity {
+    Common,
+    Uncommon,
+    Rare,
+    Epic,
+    Legendary,
+}
+
+impl Display for Rarity {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{self:?}")
+    }
+}
+
+impl Rarity {
+    /// Roll a rarity. Higher levels slightly improve the odds.
+    pub fn roll(player_level: usize) -> Self {
+        let roll: usize = random_num(1, 100) + (player_level / 10).min(20);
+
+        match roll {
+            0..=50 => Self::Common,
+            51..=75 => Self::Uncommon,
+            76..=90 => Self::Rare,
+            91..=98 => Self::Epic,
+            _ => Self::Legendary,
+        }
+    }
+
+    pub fn multiplier(self) -> usize {
+        match self {
+            Self::Common => 1,
+            Self::Uncommon => 2,
+            Self::Rare => 3,
+            Self::Epic => 5,
+            Self::Legendary => 8,
+        }
+    }
+
+    pub fn affix_count(self) -> usize {
+        self as usize
+    }
+}
+
+/// Bonus effects rolled onto loot. Values are percentages except for `Defense`.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+pub enum Affix {
+    Lifesteal(usize),
+    CriticalChance(usize),
+    Defense(usize),
+    GoldFind(usize),
+}
+
+impl Display for Affix {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Lifesteal(percent) => write!(f, "+{percent}% Lifesteal"),
+            Self::CriticalChance(percent) => write!(f, "+{percent}% Crit"),
+            Self::Defense(defense) => write!(f, "+{defense} Defense"),
+            Self::GoldFind(percent) => write!(f, "+{percent}% Gold Find"),
+        }
+    }
+}
+
+impl Affix {
+    fn roll(rarity: Rarity) -> Self {
+        let power: usize = rarity.multiplier();
+
+        match random_num(0, 3) {
+            0 => Self::Lifesteal(random_num(1, 3) * power),
+            1 => Self::CriticalChance(random_num(1, 3) * power),
+            2 => Self::Defense(random_num(2, 5) * power),
+            _ => Self::GoldFind(random_num(5, 10) * power),
+        }
+    }
+}
+
+/// Whether a piece of loot is held as a weapon or worn in an armor slot.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+pub enum Kind {
+    Weapon,
+    Armor(Slot),
+}
+
+impl Display for Kind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Weapon => write!(f, "Weapon"),
+            Self::Armor(slot) => write!(f, "{slot}"),
+        }
+    }
+}
+
+impl Kind {
+    fn random() -> Self {
+        let slots: Vec<Slot> = Slot::iter().collect();
+        let choice: usize = random_num(0, slots.len());
+
+        match slots.get(choice) {
+            Some(slot) => Self::Armor(*slot),
+            None => Self::Weapon,
+        }
+    }
+
+    fn base_name(self) -> &'static str {
+        match self {
+            Self::Weapon => ["Blade", "Axe", "Mace", "Spear"][random_num(0, 3)],
+            Self::Armor(Slot::Helmet) => "Helm",
+            Self::Armor(Slot::Chest) => "Cuirass",
+            Self::Armor(Slot::Legs) => "Greaves",
+            Self::Armor(Slot::Boots) => "Boots",
+            Self::Armor(Slot::Gloves) => "Gauntlets",
+            Self::Armor(Slot::Shield) => "Shield",
+            Self::Armor(Slot::Ring) => "Ring",
+            Self::Armor(Slot::Amulet) => "Amulet",
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct Gear {
+    pub id: usize,
+    pub name: String,
+    pub kind: Kind,
+    pub rarity: Rarity,
+    pub damage: usize,
+    pub defense: usize,
+    pub durability: usize,
+    pub default_durability: usize,
+    pub equipped: bool,
+    pub affixes: Vec<Affix>,
+}
+
+impl Display for Gear {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} ({} {})", self.name, self.rarity, self.kind)
+    }
+}
+
+impl Gear {
+    /// Generate a random piece of gear scaled to the player's level.
+    pub fn generate(player_level: usize) -> Self {
+        let rarity: Rarity = Rarity::roll(player_level);
+        let base: usize = (5 + player_level * 2) * rarity.multiplier();
+
+        let (name, kind) = if rarity == Rarity::Legendary {
+            let (name, kind) = UNIQUES[random_num(0, UNIQUES.len() - 1)];
+            (name.to_string(), kind)
+        } else {
+            let kind = Kind::random();
+            (format!("{} {}", rarity, kind.base_name()), kind)
+        };
+
+        let (damage, defense) = match kind {
+            Kind::Weapon => (base, 0),
+            Kind::Armor(Slot::Ring | Slot::Amulet) => (base / 2, base / 4),
+            Kind::Armor(_) => (0, base / 2),
+        };
+
+        let durability: usize = 100 * rarity.multiplier();
+
+        Self {
+            id: 0,
+            name,
+            kind,
+            rarity,
+            damage,
+            defense,
+            durability,
+            default_durability: durability,
+            equipped: false,
+            affixes: (0..rarity.affix_count()).map(|_| Affix::roll(rarity)).collect(),
+        }
+    }
+
+    /// Gold received for salvaging the gear.
+    pub fn value(&self) -> usize {
+        (self.damage + self.defense) * self.rarity.multiplier() * 5
+    }
+
+    /// Returns true if the gear broke.
+    pub fn decrease_durability(&mut self) -> bool {
+        let random_damage: usize = random_num(1, 4);
+
+        if self.durability < random_damage {
+            println!("Your {} broke!", self.name);
+            return true;
+        }
+
+        self.durability -= random_damage;
+        false
+    }
+
+    fn table_row(&self) -> String {
+        let affixes: Vec<String> = self.affixes.iter().map(ToString::to_string).collect();
+
+        format!(
+            "{},{},{},{},{},{},{},{}/{},{}",
+            self.id,
+            self.name,
+            self.kind,
+            self.rarity,
+            checkmark(self.equipped),
+            self.damage,
+            self.defense,
+            self.durability,
+            self.default_durability,
+            affixes.join(" "),
+        )
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+pub struct Inventory {
+    pub next_id: usize,
+    pub gear: Vec<Gear>,
+}
+
+impl Inventory {
+    pub fn reset(&mut self) {
+        *self = Self::default();
+    }
+
+    pub fn table(&self) {
+        let mut strings: Vec<String> =
+            vec!["ID,Name,Slot,Rarity,Equipped,Damage,Defense,Durability,Affixes".to_string()];
+
+        for gear in &self.gear {
+            strings.push(gear.table_row());
+        }
+
+        csv_table(&strings);
+    }
+
+    /// Store a new piece of gear, assigning it a unique id.
+    pub fn add(&mut self, mut gear: Gear) -> usize {
+        self.next_id += 1;
+        gear.id = self.next_id;
+        gear.equipped = false;
+
+        self.gear.push(gear);
+        self.next_id
+    }
+
+    pub fn get(&mut self, id: usize) -> Option<&mut Gear> {
+        self.gear.iter_mut().find(|gear| gear.id == id)
+    }
+
+    pub fn remove(&mut self, id: usize) -> Option<Gear> {
+        let index = self.gear.iter().position(|gear| gear.id == id)?;
+        Some(self.gear.remove(index))
+    }
+
+    pub fn equipped(&self) -> impl Iterator<Item = &Gear> {
+        self.gear.iter().filter(|gear| gear.equipped)
+    }
+
+    /// The equipped loot weapon, if any.
+    pub fn weapon(&mut self) -> Option<&mut Gear> {
+        self.gear
+            .iter_mut()
+            .find(|gear| gear.equipped && gear.kind == Kind::Weapon)
+    }
+
+    /// Sum an affix across every equipped piece of loot.
+    pub fn affix_total(&self, value: fn(&Affix) -> Option<usize>) -> usize {
+        self.equipped()
+            .flat_map(|gear| gear.affixes.iter())
+            .filter_map(value)
+            .sum()
+    }
+
+    pub fn lifesteal(&self) -> usize {
+        self.affix_total(|affix| match affix {
+            Affix::Lifesteal(percent) => Some(*percent),
+            _ => None,
+        })
+    }
+
+    pub fn critical_chance(&self) -> usize {
+        self.affix_total(|affix| match affix {
+            Affix::CriticalChance(percent) => Some(*percent),
+            _ => None,
+        })
+    }
+
+    pub fn gold_find(&self) -> usize {
+        self.affix_total(|affix| match affix {
+            Affix::GoldFind(percent) => Some(*percent),
+            _ => None,
+        })
+    }
+
+    /// Defense of equipped loot armor, including defense affixes.
+    pub fn defense(&self) -> usize {
+        let affixes = self.affix_total(|affix| match affix {
+            Affix::Defense(defense) => Some(*defense),
+            _ => None,
+        });
+
+        self.equipped().map(|gear| gear.defense).sum::<usize>() + affixes
+    }
+
+    /// Damage bonus from equipped loot accessories (not weapons).
+    pub fn bonus_damage(&self) -> usize {
+        self.equipped()
+            .filter(|gear| gear.kind != Kind::Weapon)
+            .map(|gear| gear.damage)
+            .sum()
+    }
+
+    /// Salvage a piece of gear for gold.
+    pub fn salvage(player: &mut Player, id: usize) -> error::Result<usize> {
+        let Some(gear) = player.loot.get(id) else {
+            return Err(Box::new(error::Inventory::ItemNotExist));
+        };
+
+        if gear.equipped {
+            return Err(Box::new(error::Miscellaneous::Custom(
+                "Un-equip this item before salvaging it.",
+            )));
+        }
+
+        let value: usize = gear.value();
+
+        player.loot.remove(id);
+        Ledger::credit(player, Source::Salvage, Account::Wallet, value);
+
+        Ok(value)
+    }
+}
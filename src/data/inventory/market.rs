@@ -0,0 +1,150 @@
+/*!
+# Market
+
+Trading Post prices follow supply and demand. Buying an item raises its
+price, selling lowers it, and every in-game day prices drift back toward
+the catalog price. The price at the start of each day is kept so players
+can watch the market move.
+
+Each unit bought or sold shifts the price by 1%, up to 50% below or 150%
+above the catalog price. The Trading Post buys items back for half of the
+market price.
+*/
+use crate::data::catalog::Catalog;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// Lowest price shift, as a percentage of the catalog price.
+pub const MIN_PRESSURE: isize = -50;
+
+/// Highest price shift, as a percentage of the catalog price.
+pub const MAX_PRESSURE: isize = 150;
+
+/// Number of daily prices kept for each item.
+pub const HISTORY_DAYS: usize = 7;
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
+pub struct Listing {
+    /// Percentage the price is shifted away from the catalog price.
+    pub pressure: isize,
+
+    /// Price at the start of each of the last few days, oldest first.
+    pub history: Vec<usize>,
+}
+
+/// Market listings keyed by catalog item id.
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
+#[serde(transparent)]
+pub struct Market {
+    pub listings: BTreeMap<String, Listing>,
+}
+
+impl Market {
+    fn base_price(id: &str) -> Option<usize> {
+        Catalog::get().item(id).map(|item| item.price)
+    }
+
+    fn price_at(base: usize, pressure: isize) -> usize {
+        let percent: usize = (100 + pressure.clamp(MIN_PRESSURE, MAX_PRESSURE)).unsigned_abs();
+
+        (base * percent / 100).max(1)
+    }
+
+    fn pressure(&self, id: &str) -> isize {
+        self.listings.get(id).map_or(0, |listing| listing.pressure)
+    }
+
+    /// Price shift caused by trading a quantity, capped at the pressure range.
+    fn shift(quantity: usize) -> isize {
+        isize::try_from(quantity).map_or(MAX_PRESSURE - MIN_PRESSURE, |shift| {
+            shift.min(MAX_PRESSURE - MIN_PRESSURE)
+        })
+    }
+
+    /// Current price of a single unit.
+    pub fn price(&self, id: &str) -> Option<usize> {
+        Self::base_price(id).map(|base| Self::price_at(base, self.pressure(id)))
+    }
+
+    /// Lowest price a single unit can ever reach, when the market has bottomed out.
+    pub fn floor_price(id: &str) -> Option<usize> {
+        Self::base_price(id).map(|base| Self::price_at(base, MIN_PRESSURE))
+    }
+
+    /// Total cost of buying a quantity, priced as the market rises during the purchase.
+    pub fn buy_quote(&self, id: &str, quantity: usize) -> Option<usize> {
+        let base: usize = Self::base_price(id)?;
+        let average: usize = Self::price_at(base, self.pressure(id) + Self::shift(quantity) / 2);
+
+        Some(quantity * average)
+    }
+
+    /// Total paid for selling a quantity, priced as the market falls during the sale.
+    pub fn sell_quote(&self, id: &str, quantity: usize) -> Option<usize> {
+        let base: usize = Self::base_price(id)?;
+        let average: usize = Self::price_at(base, self.pressure(id) - Self::shift(quantity) / 2);
+
+        Some(quantity * (average / 2))
+    }
+
+    fn trade(&mut self, id: &str, shift: isize) {
+        let listing = self.listings.entry(id.to_string()).or_default();
+
+        listing.pressure = (listing.pressure + shift).clamp(MIN_PRESSURE, MAX_PRESSURE);
+    }
+
+    pub fn record_buy(&mut self, id: &str, quantity: usize) {
+        self.trade(id, Self::shift(quantity));
+    }
+
+    pub fn record_sell(&mut self, id: &str, quantity: usize) {
+        self.trade(id, -Self::shift(quantity));
+    }
+
+    /// Prices from previous days, oldest first.
+    pub fn history(&self, id: &str) -> &[usize] {
+        self.listings.get(id).map_or(&[], |listing| &listing.history)
+    }
+
+    /// Record each item's price and let it recover a quarter of the way back to the catalog price, once per day.
+    pub fn new_days(&mut self, days: usize) {
+        for _ in 0..days {
+            for item in &Catalog::get().items {
+                let price: Option<usize> = self.price(&item.id);
+                let listing = self.listings.entry(item.id.clone()).or_default();
+
+                listing.history.extend(price);
+
+                if listing.history.len() > HISTORY_DAYS {
+                    listing.history.remove(0);
+                }
+
+                let recovery: isize = listing.pressure / 4;
+
+                listing.pressure -= if recovery == 0 {
+                    listing.pressure.signum()
+                } else {
+                    recovery
+                };
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Market;
+
+    #[test]
+    fn prices_recover() {
+        let mut market = Market::default();
+        let base = market.price("bait").expect("Bait should be cataloged.");
+
+        market.record_buy("bait", 1_000);
+        assert!(market.price("bait") > Some(base));
+
+        market.new_days(1_000);
+        assert_eq!(market.price("bait"), Some(base));
+        assert!(!market.history("bait").is_empty());
+    }
+}
@@ -0,0 +1,269 @@
+/*!
+# Vault Storage
+
+Items and gear kept safe in the bank vault. Stored goods are not lost when
+the player dies in hardmode or the profile is reset, but the vault only has
+a few slots. More slots
+can be bought, each costing more than the last.
+
+Every stack of items, weapon, armor piece, or piece of loot uses one slot.
+*/
+use crate::{
+    data::{
+        catalog::Catalog,
+        inventory::{
+            armor,
+            bank::Account,
+            ledger::{Ledger, Source},
+            loot::Gear,
+            weapons,
+        },
+        player::Player,
+    },
+    prelude::{csv_table, error},
+};
+use serde::{Deserialize, Serialize};
+use std::fmt::Display;
+
+/// Slots every vault starts with.
+pub const STARTING_SLOTS: usize = 5;
+
+/// Most slots a vault can be expanded to.
+pub const MAX_SLOTS: usize = 30;
+
+/// Price of the first extra slot. Each slot after costs this much more.
+pub const SLOT_PRICE: usize = 500;
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub enum Stored {
+    Item { id: String, quantity: usize },
+    Weapon { id: String, data: weapons::Data },
+    Armor { id: String, data: armor::Data },
+    Loot(Gear),
+}
+
+impl Display for Stored {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Item { id, quantity } => write!(f, "Item,{} x{quantity}", Catalog::get().item_name(id)),
+            Self::Weapon { data, .. } => write!(f, "Weapon,{} +{}", data.name, data.upgrade),
+            Self::Armor { data, .. } => write!(f, "Armor,{} +{}", data.name, data.upgrade),
+            Self::Loot(gear) => write!(f, "Loot,{gear}"),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct Storage {
+    pub slots: usize,
+    pub stored: Vec<Stored>,
+}
+
+impl Default for Storage {
+    fn default() -> Self {
+        Self {
+            slots: STARTING_SLOTS,
+            stored: vec![],
+        }
+    }
+}
+
+impl Storage {
+    pub fn table(&self) {
+        let mut strings: Vec<String> = vec!["Slot,Type,Stored".to_string()];
+
+        for (slot, stored) in self.stored.iter().enumerate() {
+            strings.push(format!("{},{stored}", slot + 1));
+        }
+
+        csv_table(&strings);
+        println!("Slots Used: {}/{}\n", self.stored.len(), self.slots);
+    }
+
+    /// Price of the next vault slot.
+    pub fn slot_price(&self) -> usize {
+        SLOT_PRICE * (self.slots + 1 - STARTING_SLOTS)
+    }
+
+    pub fn buy_slot(player: &mut Player) -> error::Result<()> {
+        let price: usize = player.storage.slot_price();
+
+        if player.storage.slots >= MAX_SLOTS {
+            return Err(Box::new(error::Bank::StorageMaxed));
+        }
+
+        if player.bank.wallet < price {
+            return Err(Box::new(error::Inventory::NotEnoughGold));
+        }
+
+        Ledger::debit(player, Source::Storage, Account::Wallet, price);
+        player.storage.slots += 1;
+        Ok(())
+    }
+
+    fn check_space(&self) -> error::Result<()> {
+        if self.stored.len() >= self.slots {
+            return Err(Box::new(error::Bank::StorageFull));
+        }
+
+        Ok(())
+    }
+
+    /// Move items into the vault, adding to an existing stack if there is one.
+    pub fn store_item(player: &mut Player, id: &str, quantity: usize) -> error::Result<()> {
+        if player.items.quantity(id) < quantity || quantity == 0 {
+            return Err(Box::new(error::Inventory::NotEnoughItem(Catalog::get().item_name(id))));
+        }
+
+        let stack = player.storage.stored.iter_mut().find_map(|stored| match stored {
+            Stored::Item {
+                id: stored_id,
+                quantity,
+            } if stored_id == id => Some(quantity),
+            _ => None,
+        });
+
+        if let Some(stored_quantity) = stack {
+            *stored_quantity += quantity;
+        } else {
+            player.storage.check_space()?;
+            player.storage.stored.push(Stored::Item {
+                id: id.to_string(),
+                quantity,
+            });
+        }
+
+        *player.items.get(id) -= quantity;
+        Ok(())
+    }
+
+    pub fn store_weapon(player: &mut Player, id: &str) -> error::Result<()> {
+        let weapon = player.weapons.get(id);
+
+        if !weapon.owns {
+            return Err(Box::new(error::Inventory::ItemNotOwned));
+        }
+
+        if weapon.equipped {
+            return Err(Box::new(error::Miscellaneous::Custom(
+                "Un-equip this item before storing it.",
+            )));
+        }
+
+        player.storage.check_space()?;
+
+        if let Some(data) = player.weapons.weapons.remove(id) {
+            player.storage.stored.push(Stored::Weapon {
+                id: id.to_string(),
+                data,
+            });
+        }
+
+        Ok(())
+    }
+
+    pub fn store_armor(player: &mut Player, id: &str) -> error::Result<()> {
+        let armor = player.armor.get(id);
+
+        if !armor.owns {
+            return Err(Box::new(error::Inventory::ItemNotOwned));
+        }
+
+        if armor.equipped {
+            return Err(Box::new(error::Miscellaneous::Custom(
+                "Un-equip this item before storing it.",
+            )));
+        }
+
+        player.storage.check_space()?;
+
+        if let Some(data) = player.armor.armor.remove(id) {
+            player.storage.stored.push(Stored::Armor {
+                id: id.to_string(),
+                data,
+            });
+        }
+
+        Ok(())
+    }
+
+    pub fn store_loot(player: &mut Player, id: usize) -> error::Result<()> {
+        let Some(gear) = player.loot.get(id) else {
+            return Err(Box::new(error::Inventory::ItemNotExist));
+        };
+
+        if gear.equipped {
+            return Err(Box::new(error::Miscellaneous::Custom(
+                "Un-equip this item before storing it.",
+            )));
+        }
+
+        player.storage.check_space()?;
+
+        if let Some(gear) = player.loot.remove(id) {
+            player.storage.stored.push(Stored::Loot(gear));
+        }
+
+        Ok(())
+    }
+
+    /// Take whatever is in a slot back out of the vault.
+    pub fn retrieve(player: &mut Player, slot: usize) -> error::Result<()> {
+        let Some(stored) = player.storage.stored.get(slot) else {
+            return Err(Box::new(error::Inventory::ItemNotExist));
+        };
+
+        // Gear from a content pack that was since uninstalled can't come back out.
+        match stored {
+            Stored::Weapon { id, .. } if Catalog::get().weapon(id).is_none() => {
+                return Err(Box::new(error::Inventory::ItemNotExist));
+            }
+            Stored::Armor { id, .. } if Catalog::get().armor(id).is_none() => {
+                return Err(Box::new(error::Inventory::ItemNotExist));
+            }
+            Stored::Weapon { id, .. } if player.weapons.weapons.get(id).is_some_and(|weapon| weapon.owns) => {
+                return Err(Box::new(error::Inventory::ItemOwned));
+            }
+            Stored::Armor { id, .. } if player.armor.armor.get(id).is_some_and(|armor| armor.owns) => {
+                return Err(Box::new(error::Inventory::ItemOwned));
+            }
+            _ => {}
+        }
+
+        match player.storage.stored.remove(slot) {
+            Stored::Item { id, quantity } => *player.items.get(id) += quantity,
+            Stored::Weapon { id, data } => {
+                player.weapons.weapons.insert(id, data);
+            }
+            Stored::Armor { id, data } => {
+                player.armor.armor.insert(id, data);
+            }
+            Stored::Loot(gear) => {
+                player.loot.add(gear);
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn uninstalled_pack_gear_stays_in_the_vault() {
+        use super::{Storage, Stored};
+        use crate::data::{catalog::Catalog, inventory::weapons, player::Player};
+
+        let mut player = Player::default();
+        let data = weapons::Data::from(&Catalog::get().weapons[0]);
+
+        player.storage.stored.push(Stored::Weapon {
+            id: "uninstalled_pack_sword".to_string(),
+            data,
+        });
+
+        assert!(Storage::retrieve(&mut player, 0).is_err());
+        assert_eq!(player.storage.stored.len(), 1);
+        assert!(!player.weapons.weapons.contains_key("uninstalled_pack_sword"));
+    }
+}
@@ -0,0 +1,162 @@
+/*!
+# Tools
+
+Fishing, woodcutting, and mining need the right tool. Tools are bought from
+the Trading Post and wear down with every shift of guild work until they
+break and must be replaced. Ranked members of the guild that uses a tool
+buy it at a discount. Prices and durability come from the catalog.
+*/
+use crate::{
+    data::{
+        catalog::{Catalog, ToolDef},
+        guilds::{Guild, Rank},
+        inventory::{
+            bank::Account,
+            ledger::{Ledger, Source},
+        },
+        player::Player,
+        thieving,
+    },
+    panic_menu,
+    prelude::{csv_table, error},
+};
+use serde::{Deserialize, Serialize};
+use std::{collections::BTreeMap, fmt::Display};
+use strum::IntoEnumIterator;
+use strum_macros::EnumIter;
+
+#[derive(Serialize, Deserialize, PartialEq, PartialOrd, Eq, Ord, Clone, Copy, Debug, EnumIter)]
+pub enum Tool {
+    Rod,
+    Axe,
+    Pickaxe,
+}
+
+impl Display for Tool {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Tool::Rod => write!(f, "Fishing Rod"),
+            other => write!(f, "{other:?}"),
+        }
+    }
+}
+
+impl Tool {
+    /// Guild whose members get a rank discount on the tool.
+    pub fn guild(self) -> Guild {
+        match self {
+            Tool::Rod => Guild::Fishing,
+            Tool::Axe => Guild::Woodcutting,
+            Tool::Pickaxe => Guild::Mining,
+        }
+    }
+
+    /// Price after the player's guild rank discount, raised by any bounty.
+    pub fn price_for(self, player: &Player) -> usize {
+        let discount: usize = player.guilds.rank(self.guild()).map_or(0, Rank::discount);
+
+        thieving::markup(player, self.price() - self.price() * discount / 100)
+    }
+
+    fn definition(self) -> &'static ToolDef {
+        let Some(definition) = Catalog::get().tools.get(&self) else {
+            panic_menu!("The {} is not in the catalog.", self);
+        };
+
+        definition
+    }
+
+    pub fn price(self) -> usize {
+        self.definition().price
+    }
+
+    /// Shifts of work the tool lasts before breaking.
+    pub fn durability(self) -> usize {
+        self.definition().durability
+    }
+}
+
+/// Durability left on each owned tool.
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
+#[serde(transparent)]
+pub struct Tools {
+    pub durability: BTreeMap<Tool, usize>,
+}
+
+impl Tools {
+    pub fn reset(&mut self) {
+        *self = Self::default();
+    }
+
+    pub fn owns(&self, tool: Tool) -> bool {
+        self.durability.contains_key(&tool)
+    }
+
+    /// Wear the tool down by one use. Returns true if it broke.
+    pub fn wear(&mut self, tool: Tool) -> bool {
+        let Some(durability) = self.durability.get_mut(&tool) else {
+            return false;
+        };
+
+        *durability = durability.saturating_sub(1);
+
+        if *durability == 0 {
+            self.durability.remove(&tool);
+            return true;
+        }
+
+        false
+    }
+
+    pub fn shop_table(player: &Player) {
+        let mut strings: Vec<String> = vec!["Tool,Price,Durability".to_string()];
+
+        for tool in Tool::iter() {
+            let durability: String = player
+                .tools
+                .durability
+                .get(&tool)
+                .map_or_else(|| "-".to_string(), |left| format!("{left}/{}", tool.durability()));
+
+            strings.push(format!("{tool},{},{durability}", tool.price_for(player)));
+        }
+
+        csv_table(&strings);
+    }
+
+    /// Buy a tool, or replace a worn one with a new one.
+    pub fn buy(player: &mut Player, tool: Tool) -> error::Result<()> {
+        if player.tools.durability.get(&tool) == Some(&tool.durability()) {
+            return Err(Box::new(error::Inventory::ItemOwned));
+        }
+
+        let price: usize = tool.price_for(player);
+
+        if player.bank.wallet < price {
+            return Err(Box::new(error::Inventory::NotEnoughGold));
+        }
+
+        Ledger::debit(player, Source::TradingPost, Account::Wallet, price);
+        player.tools.durability.insert(tool, tool.durability());
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn tools_break_when_worn_out() {
+        use super::{Tool, Tools};
+
+        let mut tools = Tools::default();
+
+        assert!(!tools.wear(Tool::Axe));
+
+        tools.durability.insert(Tool::Axe, 2);
+
+        assert!(!tools.wear(Tool::Axe));
+        assert!(tools.owns(Tool::Axe));
+        assert!(tools.wear(Tool::Axe));
+        assert!(!tools.owns(Tool::Axe));
+    }
+}
@@ -1,35 +1,21 @@
 use crate::utils::tui::{checkmark, csv_table};
 use rand::{thread_rng, Rng};
-use serde::{Deserialize, Serialize};
+use serde::{Deserialize, Deserializer, Serialize};
 use std::{collections::BTreeMap, fmt::Display};
 
-use crate::data::player::Player;
+use crate::data::{
+    catalog::{Catalog, WeaponDef},
+    inventory::{
+        bank::Account,
+        ledger::{Ledger, Source},
+    },
+    player::Player,
+    thieving,
+};
+use crate::panic_menu;
 use crate::prelude::{error, select};
 use std::result::Result;
 
-#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, PartialOrd, Eq, Ord)]
-pub enum Types {
-    Wooden,
-    Bronze,
-    Iron,
-    Steel,
-    Mystic,
-    WizardStaff,
-}
-
-impl Display for Types {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(
-            f,
-            "{}",
-            match self {
-                Self::WizardStaff => "Wizard Staff".to_string(),
-                sword => format!("{sword:?} Sword"),
-            }
-        )
-    }
-}
-
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
 pub struct Data {
     pub owns: bool,
@@ -37,18 +23,21 @@ pub struct Data {
     pub damage: usize,
     pub durability: usize,
     pub default_durability: usize,
-    pub flag: Types,
+    #[serde(default)]
+    pub upgrade: usize,
+    pub name: String,
 }
 
-impl Default for Data {
-    fn default() -> Self {
+impl From<&WeaponDef> for Data {
+    fn from(weapon: &WeaponDef) -> Self {
         Self {
             owns: false,
             equipped: false,
-            damage: 5,
-            durability: 50,
-            default_durability: 50,
-            flag: Types::Wooden,
+            damage: weapon.damage,
+            durability: weapon.durability,
+            default_durability: weapon.durability,
+            upgrade: 0,
+            name: weapon.name.clone(),
         }
     }
 }
@@ -57,26 +46,18 @@ impl Display for Data {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(
             f,
-            "{},{},{},{},{}",
-            self.flag,
+            "{},{},{},+{},{},{}",
+            self.name,
             checkmark(self.owns),
             checkmark(self.equipped),
-            self.damage,
+            self.upgrade,
+            self.total_damage(),
             self.durability,
         )
     }
 }
 
 impl Data {
-    pub fn new(damage: usize, durability: usize, flag: Types) -> Self {
-        Self {
-            damage,
-            durability,
-            flag,
-            ..Default::default()
-        }
-    }
-
     pub fn decrease_durability(&mut self) {
         let random_damage = thread_rng().gen_range(1..5);
 
@@ -87,67 +68,126 @@ impl Data {
         }
     }
 
+    /// Durability lost since the item was last bought or repaired.
+    pub fn missing_durability(&self) -> usize {
+        self.default_durability.saturating_sub(self.durability)
+    }
+
+    /// Gold needed to fully repair the item. A fully broken item costs
+    /// half of its shop price.
+    pub fn repair_cost(&self, price: usize) -> usize {
+        if self.missing_durability() == 0 {
+            return 0;
+        }
+
+        (price * self.missing_durability() / self.default_durability / 2).max(1)
+    }
+
+    pub fn repair(&mut self) {
+        self.durability = self.default_durability;
+    }
+
     pub fn break_weapon(&mut self) {
-        println!("Your {} broke!", self.flag);
+        println!("Your {} broke!", self.name);
         self.owns = false;
+        self.upgrade = 0;
         self.durability = self.default_durability;
     }
+
+    /// Damage plus a 10% bonus of the base damage for each smithing upgrade level,
+    /// and never less than 1 per level so weak weapons still gain from upgrades.
+    pub fn total_damage(&self) -> usize {
+        self.damage + (self.damage * self.upgrade / 10).max(self.upgrade)
+    }
 }
 
-#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
-pub struct Inventory {
-    pub wooden_sword: Data,
-    pub bronze_sword: Data,
-    pub iron_sword: Data,
-    pub steel_sword: Data,
-    pub mystic_sword: Data,
-    pub wizard_staff: Data,
+/// A weapon as saved in a profile. Profiles from before the catalog have no
+/// name, so their stats are taken from the catalog instead.
+#[derive(Deserialize)]
+struct Saved {
+    owns: bool,
+    equipped: bool,
+    damage: usize,
+    durability: usize,
+    default_durability: usize,
+    #[serde(default)]
+    upgrade: usize,
+    name: Option<String>,
 }
 
-impl Default for Inventory {
-    fn default() -> Self {
-        Self {
-            wooden_sword: Data::new(10, 100, Types::Wooden),
-            bronze_sword: Data::new(20, 150, Types::Bronze),
-            iron_sword: Data::new(50, 200, Types::Iron),
-            steel_sword: Data::new(200, 500, Types::Steel),
-            mystic_sword: Data::new(500, 1_000, Types::Mystic),
-            wizard_staff: Data::new(1_000, 2_000, Types::WizardStaff),
+impl Saved {
+    fn into_data(self, id: &str) -> Data {
+        if let (None, Some(weapon)) = (&self.name, Catalog::get().weapon(id)) {
+            return Data {
+                owns: self.owns,
+                equipped: self.equipped,
+                durability: self.durability.min(weapon.durability),
+                upgrade: self.upgrade,
+                ..Data::from(weapon)
+            };
+        }
+
+        Data {
+            owns: self.owns,
+            equipped: self.equipped,
+            damage: self.damage,
+            durability: self.durability,
+            default_durability: self.default_durability,
+            upgrade: self.upgrade,
+            name: self.name.unwrap_or_else(|| id.to_string()),
         }
     }
 }
 
+fn migrate<'de, D: Deserializer<'de>>(deserializer: D) -> Result<BTreeMap<String, Data>, D::Error> {
+    let saved: BTreeMap<String, Saved> = BTreeMap::deserialize(deserializer)?;
+
+    Ok(saved
+        .into_iter()
+        .map(|(id, weapon)| {
+            let data: Data = weapon.into_data(&id);
+            (id, data)
+        })
+        .collect())
+}
+
+/// Weapons keyed by catalog id.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Default)]
+#[serde(transparent)]
+pub struct Inventory {
+    #[serde(deserialize_with = "migrate")]
+    pub weapons: BTreeMap<String, Data>,
+}
+
 impl Inventory {
     pub fn reset(&mut self) {
         *self = Self::default();
     }
 
-    pub fn table(&self) {
-        csv_table(&[
-            "Weapon,Owned,Equipped,Damage,Durability".to_string(),
-            self.wooden_sword.to_string(),
-            self.bronze_sword.to_string(),
-            self.iron_sword.to_string(),
-            self.steel_sword.to_string(),
-            self.mystic_sword.to_string(),
-            self.wizard_staff.to_string(),
-        ]);
-    }
-
-    pub fn get(&mut self, item_flag: &Types) -> &mut Data {
-        match item_flag {
-            Types::Bronze => &mut self.bronze_sword,
-            Types::Iron => &mut self.iron_sword,
-            Types::Mystic => &mut self.mystic_sword,
-            Types::Steel => &mut self.steel_sword,
-            Types::WizardStaff => &mut self.wizard_staff,
-            Types::Wooden => &mut self.wooden_sword,
+    pub fn table(&mut self) {
+        let mut strings: Vec<String> = vec!["Weapon,Owned,Equipped,Upgrade,Damage,Durability".to_string()];
+
+        for weapon in &Catalog::get().weapons {
+            strings.push(self.get(&weapon.id).to_string());
         }
+
+        csv_table(&strings);
+    }
+
+    /// Get a weapon by catalog id, adding it to the inventory if needed.
+    pub fn get(&mut self, id: &str) -> &mut Data {
+        self.weapons.entry(id.to_string()).or_insert_with(|| {
+            let Some(weapon) = Catalog::get().weapon(id) else {
+                panic_menu!("Weapon '{}' is not in the catalog.", id);
+            };
+
+            Data::from(weapon)
+        })
     }
 
     /// For use in developer mode only
-    pub fn toggle_own(&mut self, item_flag: &Types) {
-        let item = self.get(item_flag);
+    pub fn toggle_own(&mut self, id: &str) {
+        let item = self.get(id);
 
         item.owns = !item.owns;
     }
@@ -156,63 +196,57 @@ impl Inventory {
 // -------------------------------------------------- Economy -------------------------------------------------- //
 
 impl Inventory {
-    fn shop() -> BTreeMap<Types, usize> {
-        BTreeMap::from([
-            (Types::Wooden, 10),
-            (Types::Bronze, 50),
-            (Types::Iron, 100),
-            (Types::Steel, 500),
-            (Types::Mystic, 1_000),
-            (Types::WizardStaff, 10_000),
-        ])
+    fn price(id: &str) -> Option<usize> {
+        Catalog::get().weapon(id).map(|weapon| weapon.price)
     }
 
     pub fn shop_table(player: &mut Player) {
-        let mut strings: Vec<String> = vec!["Item,Price,Owned".to_string()];
-
-        for (flag, price) in &Self::shop() {
-            let owned = player.weapons.get(flag).owns;
-
-            strings.push(format!("{},{},{}", flag, price, checkmark(owned)));
+        let mut strings: Vec<String> = vec!["Item,Price,Owned,Durability".to_string()];
+
+        for catalog_weapon in &Catalog::get().weapons {
+            let price: usize = thieving::markup(player, catalog_weapon.price);
+            let weapon = player.weapons.get(&catalog_weapon.id);
+
+            strings.push(format!(
+                "{},{},{},{}/{}",
+                weapon.name,
+                price,
+                checkmark(weapon.owns),
+                weapon.durability,
+                weapon.default_durability
+            ));
         }
 
         csv_table(&strings);
         println!("Gold: {}\n", player.bank.wallet);
     }
 
-    pub fn select() -> Types {
-        let shop = Self::shop();
-        let items: Vec<String> = shop.keys().map(std::string::ToString::to_string).collect();
+    /// Select a weapon from the catalog, returning its id.
+    pub fn select() -> String {
+        let weapons = &Catalog::get().weapons;
+        let names: Vec<&String> = weapons.iter().map(|weapon| &weapon.name).collect();
+
+        let selector = select(&names, None);
 
-        let selector = select(&items, None);
-        let selected_item = items
+        weapons
             .get(selector)
             .expect("This shouldn't select a vector item out of bounds.")
-            .clone();
-
-        shop.iter()
-            .find(|item| item.0.to_string() == selected_item)
-            .map(|item| item.0)
-            .expect("Should return an Item Flag")
+            .id
             .clone()
     }
 
-    pub fn buy(player: &mut Player, weapon: &Types, payment: bool) -> Result<(), error::Inventory> {
-        let shop = Self::shop();
-        let price = shop.get(weapon).ok_or(error::Inventory::ItemNotExist)?;
+    pub fn buy(player: &mut Player, id: &str, payment: bool) -> Result<(), error::Inventory> {
+        let price: usize = thieving::markup(player, Self::price(id).ok_or(error::Inventory::ItemNotExist)?);
 
         if payment {
-            let gold: usize = player.bank.wallet;
-            let wallet: &mut usize = &mut player.bank.wallet;
-
-            if gold < *price {
+            if player.bank.wallet < price {
                 return Err(error::Inventory::NotEnoughGold);
             }
 
-            *wallet -= *price;
+            Ledger::debit(player, Source::WeaponsShop, Account::Wallet, price);
         }
 
-        let owns_item = &mut player.weapons.get(weapon).owns;
+        let owns_item = &mut player.weapons.get(id).owns;
 
         if *owns_item {
             return Err(error::Inventory::ItemOwned);
@@ -222,24 +256,55 @@ impl Inventory {
         Ok(())
     }
 
-    pub fn sell(player: &mut Player, weapon: &Types, payment: bool) -> Result<(), error::Inventory> {
-        let shop: BTreeMap<Types, usize> = Self::shop();
-        let price: &usize = shop.get(weapon).ok_or(error::Inventory::ItemNotExist)?;
-        let owns_item = &mut player.weapons.get(weapon).owns;
+    pub fn sell(player: &mut Player, id: &str, payment: bool) -> Result<(), error::Inventory> {
+        let price: usize = Self::price(id).ok_or(error::Inventory::ItemNotExist)?;
+        let item = player.weapons.get(id);
+
+        if !item.owns {
+            return Err(error::Inventory::ItemNotOwned);
+        }
+
+        item.owns = false;
+        item.upgrade = 0;
+        item.repair();
+
+        if payment {
+            Ledger::credit(player, Source::WeaponsShop, Account::Wallet, price / 2);
+        }
+
+        Ok(())
+    }
+
+    /// Gold the shop charges to repair an owned item, raised by any bounty.
+    pub fn repair_price(player: &mut Player, id: &str) -> Result<usize, error::Inventory> {
+        let price: usize = Self::price(id).ok_or(error::Inventory::ItemNotExist)?;
+        let item = player.weapons.get(id);
 
-        if !*owns_item {
+        if !item.owns {
             return Err(error::Inventory::ItemNotOwned);
         }
 
-        *owns_item = false;
+        if item.missing_durability() == 0 {
+            return Err(error::Inventory::NothingToRepair);
+        }
+
+        let cost: usize = item.repair_cost(price);
+
+        Ok(thieving::markup(player, cost))
+    }
+
+    pub fn repair(player: &mut Player, id: &str, payment: bool) -> Result<(), error::Inventory> {
+        let price: usize = Self::repair_price(player, id)?;
 
         if payment {
-            let wallet: &mut usize = &mut player.bank.wallet;
-            let price: usize = price / 2;
+            if player.bank.wallet < price {
+                return Err(error::Inventory::NotEnoughGold);
+            }
 
-            *wallet += price;
+            Ledger::debit(player, Source::Repairs, Account::Wallet, price);
         }
 
+        player.weapons.get(id).repair();
         Ok(())
     }
 }
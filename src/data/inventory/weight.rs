@@ -0,0 +1,97 @@
+/*!
+# Carry Weight
+
+Every item has a weight, and the player can only carry so much. Bags bought
+from the Trading Post and points in strength raise the carry capacity.
+
+Players carrying more than their capacity are encumbered: they deal half
+damage in battle and cannot buy anything from the Trading Post until they
+lighten their load.
+
+Only Trading Post purchases are capped. Items from battle, guild work,
+farming, achievements, and the vault are always added, even past the
+capacity, and leave the player encumbered instead.
+*/
+use crate::{
+    data::{
+        catalog::Catalog,
+        inventory::{
+            bank::Account,
+            ledger::{Ledger, Source},
+        },
+        player::Player,
+    },
+    prelude::error,
+};
+
+/// Weight anyone can carry without bags.
+pub const BASE_CAPACITY: usize = 300;
+
+/// Extra weight each bag holds.
+pub const BAG_CAPACITY: usize = 100;
+
+/// Most bags a player can carry.
+pub const MAX_BAGS: usize = 5;
+
+/// Price of the first bag. Each bag after costs this much more.
+pub const BAG_PRICE: usize = 250;
+
+/// Total weight of every item the player carries.
+pub fn carried(player: &Player) -> usize {
+    Catalog::get()
+        .items
+        .iter()
+        .map(|item| item.weight * player.items.quantity(&item.id))
+        .sum()
+}
+
+/// Most weight the player can carry without being encumbered, raised by bags and strength.
+pub fn capacity(player: &Player) -> usize {
+    BASE_CAPACITY + BAG_CAPACITY * player.bags + player.attributes.bonus_capacity()
+}
+
+pub fn is_encumbered(player: &Player) -> bool {
+    carried(player) > capacity(player)
+}
+
+/// Fails if adding the items would put the player over their capacity.
+/// Only checked for Trading Post purchases; other sources may overfill.
+pub fn check_room(player: &Player, id: &str, quantity: usize) -> Result<(), error::Inventory> {
+    let weight: usize = Catalog::get().item(id).map_or(0, |item| item.weight);
+
+    if carried(player) + weight * quantity > capacity(player) {
+        return Err(error::Inventory::TooHeavy);
+    }
+
+    Ok(())
+}
+
+/// Price of the next bag.
+pub fn bag_price(player: &Player) -> usize {
+    BAG_PRICE * (player.bags + 1)
+}
+
+pub fn buy_bag(player: &mut Player) -> error::Result<()> {
+    let price: usize = bag_price(player);
+
+    if player.bags >= MAX_BAGS {
+        return Err(Box::new(error::Miscellaneous::Custom(
+            "You cannot carry any more bags.",
+        )));
+    }
+
+    if player.bank.wallet < price {
+        return Err(Box::new(error::Inventory::NotEnoughGold));
+    }
+
+    Ledger::debit(player, Source::TradingPost, Account::Wallet, price);
+    player.bags += 1;
+    Ok(())
+}
+
+/// Carried weight and capacity, e.g. `Weight: 120/300`.
+pub fn summary(player: &Player) -> String {
+    let encumbered: &str = if is_encumbered(player) { " (Encumbered)" } else { "" };
+
+    format!("Weight: {}/{}{encumbered}", carried(player), capacity(player))
+}
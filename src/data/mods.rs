@@ -0,0 +1,274 @@
+/*!
+# Content Packs
+
+Content packs add enemies, items, weapons, armor, recipes, regions, and
+quests, and change guild membership and tool prices, without recompiling
+the game. Each pack is a folder in
+the `mods` folder next to the profiles folder:
+
+```text
+.albion_term_rpg/
+├── profiles/
+└── mods/
+    └── my_pack/
+        └── pack.toml
+```
+
+A `pack.toml` uses the same sections as the item catalog, including
+recipes, regions, and quests, plus enemies. Regions and quests may use
+built-in species ids like `dire_wolf` or enemies from any pack:
+
+```toml
+[[items]]
+id = "wolf_pelt"
+name = "Wolf Pelt"
+price = 40
+
+[[enemies]]
+id = "frost_wolf"
+name = "Frost Wolf"
+hp = 20
+damage = 5
+drops = { wolf_pelt = 2 }
+
+[[regions]]
+id = "frozen_north"
+name = "The Frozen North"
+level = 5
+enemies = ["frost_wolf", "white_ape"]
+
+[[quests]]
+id = "frost_wolf_pelts"
+name = "Frost Wolf Pelts"
+enemy = "frost_wolf"
+count = 3
+gold = 200
+items = { wolf_pelt = 1 }
+
+[guilds]
+Fishing = 75
+
+[tools.Rod]
+price = 40
+durability = 200
+```
+
+Packs are loaded in alphabetical order. An id that is already defined by
+the game or an earlier pack is a conflict: the new definition is skipped
+and reported when the game starts. Guild and tool prices may each be
+changed by one pack only.
+
+# Example
+
+```
+use albion_terminal_rpg::data::mods::ContentPack;
+
+let pack = ContentPack::try_from("[[items]]\nid = \"wolf_pelt\"\nname = \"Wolf Pelt\"\nprice = 40");
+
+assert!(pack.is_ok());
+```
+*/
+use crate::{
+    data::{
+        catalog::{ArmorDef, Catalog, EnemyDef, ItemDef, QuestDef, RecipeDef, RegionDef, ToolDef, WeaponDef},
+        guilds::Guild,
+        inventory::tools::Tool,
+    },
+    prelude::{error, mods_directory},
+};
+use serde::Deserialize;
+use std::{collections::BTreeMap, fs, path::Path};
+
+/// File every content pack folder must contain.
+pub const FILE_NAME: &str = "pack.toml";
+
+#[derive(Deserialize, Debug, Clone, Default, PartialEq)]
+pub struct ContentPack {
+    #[serde(skip)]
+    pub name: String,
+    #[serde(default)]
+    pub items: Vec<ItemDef>,
+    #[serde(default)]
+    pub weapons: Vec<WeaponDef>,
+    #[serde(default)]
+    pub armor: Vec<ArmorDef>,
+    #[serde(default)]
+    pub enemies: Vec<EnemyDef>,
+    #[serde(default)]
+    pub recipes: Vec<RecipeDef>,
+    #[serde(default)]
+    pub regions: Vec<RegionDef>,
+    #[serde(default)]
+    pub quests: Vec<QuestDef>,
+    #[serde(default)]
+    pub guilds: BTreeMap<Guild, usize>,
+    #[serde(default)]
+    pub tools: BTreeMap<Tool, ToolDef>,
+}
+
+impl TryFrom<&str> for ContentPack {
+    type Error = error::Catalog;
+
+    fn try_from(data: &str) -> Result<Self, Self::Error> {
+        toml::from_str(data).map_err(|error| error::Catalog::Parse(error.to_string()))
+    }
+}
+
+impl ContentPack {
+    /**
+    Read every pack in the mods folder, sorted by folder name. Packs that
+    fail to parse are skipped and described in the returned problems.
+    */
+    pub fn discover() -> (Vec<Self>, Vec<String>) {
+        let Ok(entries) = fs::read_dir(mods_directory()) else {
+            return (vec![], vec![]);
+        };
+
+        let mut folders: Vec<_> = entries.filter_map(Result::ok).map(|entry| entry.path()).collect();
+        folders.sort();
+
+        let mut packs: Vec<Self> = vec![];
+        let mut problems: Vec<String> = vec![];
+
+        for folder in folders.iter().filter(|folder| folder.is_dir()) {
+            let name: String = folder
+                .file_name()
+                .map(|name| name.to_string_lossy().to_string())
+                .unwrap_or_default();
+
+            let Ok(contents) = fs::read_to_string(Path::new(folder).join(FILE_NAME)) else {
+                problems.push(format!("Pack '{name}' has no {FILE_NAME} and was skipped."));
+                continue;
+            };
+
+            match Self::try_from(contents.as_str()) {
+                Ok(pack) => packs.push(Self { name, ..pack }),
+                Err(error) => problems.push(format!("Pack '{name}' was skipped: {error}")),
+            }
+        }
+
+        (packs, problems)
+    }
+}
+
+impl Catalog {
+    /**
+    Merge content packs into the catalog in order, recording each pack's
+    name. Returns a description of every conflict found.
+    */
+    pub fn merge(&mut self, packs: Vec<ContentPack>) -> Vec<String> {
+        fn add<T>(pack: &str, section: &str, list: &mut Vec<T>, new: Vec<T>, id: fn(&T) -> &String) -> Vec<String> {
+            let mut conflicts: Vec<String> = vec![];
+
+            for definition in new {
+                if list.iter().any(|existing| id(existing) == id(&definition)) {
+                    conflicts.push(format!(
+                        "Pack '{pack}': {section} '{}' is already defined and was skipped.",
+                        id(&definition)
+                    ));
+                } else {
+                    list.push(definition);
+                }
+            }
+
+            conflicts
+        }
+
+        let mut conflicts: Vec<String> = vec![];
+        let mut guild_owners: BTreeMap<Guild, String> = BTreeMap::new();
+        let mut tool_owners: BTreeMap<Tool, String> = BTreeMap::new();
+
+        for pack in packs {
+            let name: &str = &pack.name;
+
+            conflicts.extend(add(name, "item", &mut self.items, pack.items, |item| &item.id));
+            conflicts.extend(add(name, "weapon", &mut self.weapons, pack.weapons, |weapon| {
+                &weapon.id
+            }));
+            conflicts.extend(add(name, "armor", &mut self.armor, pack.armor, |armor| &armor.id));
+            conflicts.extend(add(name, "enemy", &mut self.enemies, pack.enemies, |enemy| &enemy.id));
+            conflicts.extend(add(name, "recipe", &mut self.recipes, pack.recipes, |recipe| {
+                &recipe.id
+            }));
+            conflicts.extend(add(name, "region", &mut self.regions, pack.regions, |region| {
+                &region.id
+            }));
+            conflicts.extend(add(name, "quest", &mut self.quests, pack.quests, |quest| &quest.id));
+
+            for (guild, price) in pack.guilds {
+                if let Some(owner) = guild_owners.get(&guild) {
+                    conflicts.push(format!(
+                        "Pack '{name}': the {guild} guild price was already changed by '{owner}' and was skipped."
+                    ));
+                    continue;
+                }
+
+                guild_owners.insert(guild, name.to_string());
+                self.guilds.insert(guild, price);
+            }
+
+            for (tool, definition) in pack.tools {
+                if let Some(owner) = tool_owners.get(&tool) {
+                    conflicts.push(format!(
+                        "Pack '{name}': the {tool} price was already changed by '{owner}' and was skipped."
+                    ));
+                    continue;
+                }
+
+                tool_owners.insert(tool, name.to_string());
+                self.tools.insert(tool, definition);
+            }
+
+            self.packs.push(pack.name);
+        }
+
+        conflicts
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::ContentPack;
+    use crate::data::catalog::Catalog;
+
+    #[test]
+    fn conflicting_packs() {
+        let mut catalog = Catalog::builtin().expect("Built-in catalog should be valid.");
+
+        let first = ContentPack {
+            name: "first".to_string(),
+            ..ContentPack::try_from("[[items]]\nid = \"bait\"\nname = \"Worms\"\nprice = 2\n[guilds]\nFishing = 1")
+                .expect("Pack should parse.")
+        };
+
+        let second = ContentPack {
+            name: "second".to_string(),
+            ..ContentPack::try_from("[guilds]\nFishing = 2").expect("Pack should parse.")
+        };
+
+        let conflicts = catalog.merge(vec![first, second]);
+
+        assert_eq!(conflicts.len(), 2);
+        assert_eq!(catalog.item_name("bait"), "Bait");
+        assert_eq!(catalog.packs, vec!["first".to_string(), "second".to_string()]);
+    }
+
+    #[test]
+    fn packs_override_tools() {
+        use crate::data::inventory::tools::Tool;
+
+        let mut catalog = Catalog::builtin().expect("Built-in catalog should be valid.");
+
+        let pack = ContentPack {
+            name: "tools".to_string(),
+            ..ContentPack::try_from("[tools.Rod]\nprice = 40\ndurability = 200").expect("Pack should parse.")
+        };
+
+        assert!(catalog.merge(vec![pack]).is_empty());
+        assert_eq!(
+            catalog.tools.get(&Tool::Rod).map(|rod| (rod.price, rod.durability)),
+            Some((40, 200))
+        );
+        assert!(catalog.validate().is_ok());
+    }
+}
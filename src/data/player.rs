@@ -5,14 +5,18 @@ Provides all the data for the player and passed around all menu functions.
 
 # Data Points
 
-- Health
-- XP/Levels
-- Achievements
-- Finances
-- Guilds/Jobs
-- Equipment, Armor, and Weapons
+- Health and potion effects
+- Class, XP/Levels and attributes
+- Achievements and lifetime statistics
+- Finances, the ledger, and bounties
+- Guilds/Jobs and farm plots
+- Quests, which survive death
+- Equipment, Armor, Weapons, Loot, and Tools
+- Vault storage, which survives death and profile resets
 - Shop items and medicine
 - Settings
+- In-game time and the Trading Post market
+- Content packs
 
 */
 use std::{fmt::Display, path::Path};
@@ -20,19 +24,39 @@ use std::{fmt::Display, path::Path};
 use crate::{
     data::{
         achievements::Achievements,
-        guilds::Guilds,
+        attributes::Attributes,
+        catalog::Catalog,
+        classes::{self, Class},
+        clock::Clock,
+        consumables::Effects,
+        farming::Farm,
+        guilds::{Guild, Guilds},
         health::Health,
-        inventory::{armor, bank::Bank, equipment::Equipment, items, weapons::Inventory},
+        inventory::{
+            armor,
+            bank::{Account, Bank},
+            equipment::Equipment,
+            items,
+            ledger::{Ledger, Source},
+            loot,
+            market::Market,
+            storage::Storage,
+            tools::Tools,
+            weapons::Inventory,
+        },
+        quests::Quests,
         settings::Settings,
-        xp::XP,
+        statistics::Statistics,
+        xp::{LevelUp, XPType, XP},
     },
     panic_menu,
     prelude::{
-        confirm, delete_file, error, page_header, pause, player_file_path, read_file, warning, write_file,
+        confirm, delete_file, error, page_header, pause, player_file_path, read_file, success, warning, write_file,
         Instructions, Logs,
     },
 };
 use serde::{Deserialize, Serialize};
+use strum::IntoEnumIterator;
 use toml as encoder;
 
 /**
@@ -42,14 +66,18 @@ Provides all the data for the player and passed around all menu functions.
 
 # Data Points
 
-- Health
-- XP/Levels
-- Achievements
-- Finances
-- Guilds/Jobs
-- Equipment, Armor, and Weapons
+- Health and potion effects
+- Class, XP/Levels and attributes
+- Achievements and lifetime statistics
+- Finances, the ledger, and bounties
+- Guilds/Jobs and farm plots
+- Quests, which survive death
+- Equipment, Armor, Weapons, Loot, and Tools
+- Vault storage, which survives death and profile resets
 - Shop items and medicine
 - Settings
+- In-game time and the Trading Post market
+- Content packs
 
 # Usage
 
@@ -69,16 +97,58 @@ let player_string = default_player.to_string();
 */
 #[derive(Serialize, Deserialize, Debug, Clone, Default)]
 pub struct Player {
+    #[serde(default)]
+    pub class: Class,
     pub health: Health,
+
+    /// Potion effects still active.
+    #[serde(default)]
+    pub effects: Effects,
     pub xp: XP,
+    #[serde(default)]
+    pub attributes: Attributes,
     pub achievements: Achievements,
+
+    /// Lifetime statistics, which survive death.
+    #[serde(default)]
+    pub statistics: Statistics,
     pub bank: Bank,
     pub guilds: Guilds,
+    #[serde(default)]
+    pub farm: Farm,
+
+    /// Quests taken and finished, which survive death.
+    #[serde(default)]
+    pub quests: Quests,
     pub equipment: Equipment,
     pub items: items::Inventory,
     pub armor: armor::Inventory,
     pub weapons: Inventory,
+    #[serde(default)]
+    pub loot: loot::Inventory,
+    #[serde(default)]
+    pub storage: Storage,
+    #[serde(default)]
+    pub tools: Tools,
+
+    /// Bags bought to raise the carry capacity.
+    #[serde(default)]
+    pub bags: usize,
+
+    /// Gold owed for crimes. Shops raise their prices until it is paid off.
+    #[serde(default)]
+    pub bounty: usize,
     pub settings: Settings,
+    #[serde(default)]
+    pub clock: Clock,
+    #[serde(default)]
+    pub market: Market,
+    #[serde(default)]
+    pub ledger: Ledger,
+
+    /// Content packs that were loaded when the profile was created.
+    #[serde(default)]
+    pub packs: Vec<String>,
 }
 
 impl std::fmt::Display for Player {
@@ -122,6 +192,7 @@ impl Player {
     pub fn new<T: Display, U: Display>(username: &T, password_hash: &U, save: bool) -> Self {
         let profile: Player = Player {
             settings: Settings::new(username, password_hash),
+            packs: Catalog::get().packs.clone(),
             ..Default::default()
         };
 
@@ -132,8 +203,26 @@ impl Player {
         profile
     }
 
+    /// Most hp the player can have, raised by vitality.
+    pub fn max_hp(&self) -> usize {
+        self.attributes.max_hp()
+    }
+
+    /// Percent chance to hit, raised by agility and class passives.
+    pub fn hit_chance(&self) -> usize {
+        classes::hit_chance(self)
+    }
+
+    /// Content packs the profile was created with that are no longer installed.
+    pub fn missing_packs(&self) -> Vec<&String> {
+        let loaded: &Vec<String> = &Catalog::get().packs;
+
+        self.packs.iter().filter(|pack| !loaded.contains(pack)).collect()
+    }
+
     /**
-    Reset all player settings, except for username and password hash.
+    Reset all player settings, except for username, password hash, and class.
+    Vault storage and the gold in the bank vault are kept as well.
 
     # Example
 
@@ -146,12 +235,17 @@ impl Player {
     ```
     */
     pub fn reset(&mut self) {
-        let new_profile = Self::new(&self.settings.username, &self.settings.password_hash, true);
+        let mut new_profile = Self::new(&self.settings.username, &self.settings.password_hash, false);
+        new_profile.class = self.class;
+        new_profile.storage = std::mem::take(&mut self.storage);
+        new_profile.bank.vault = self.bank.vault;
+        new_profile.save();
+
         *self = new_profile;
     }
 
     /**
-    Reset all progress and inventory (not settings) without deleting the profile
+    Reset all progress and inventory (not settings or vault storage) without deleting the profile
 
     # Example
 
@@ -165,22 +259,91 @@ impl Player {
     */
     pub fn die(&mut self) {
         // Wealth
-        self.bank.wallet = 0;
+        let wallet: usize = self.bank.wallet;
+        Ledger::debit(self, Source::Death, Account::Wallet, wallet);
+        self.bounty = 0;
 
         // Equipment
         self.equipment.reset();
         self.armor.reset();
         self.weapons.reset();
+        self.loot.reset();
+        self.tools.reset();
         self.items.reset();
+        self.farm.reset();
 
         // Statistics
         self.xp.reset();
+        self.attributes.reset();
         self.achievements.reset();
         self.health.reset();
+        self.effects.reset();
 
         self.save();
     }
 
+    /**
+    Move the in-game clock forward, updating everything that changes daily.
+
+    # Example
+
+    ```
+    use albion_terminal_rpg::data::player::Player;
+
+    let mut player = Player::default();
+
+    player.pass_time(1);
+    ```
+    */
+    pub fn pass_time(&mut self, hours: usize) {
+        let days: usize = self.clock.advance(hours);
+
+        Effects::expire(self);
+        self.market.new_days(days);
+        self.guilds.new_days(days);
+        Bank::new_days(self, days);
+    }
+
+    /**
+    Show a message for every level gained since the last call, along with
+    any class skills unlocked and guild promotions earned, then check for any
+    achievements earned since.
+
+    # Example
+
+    ```
+    use albion_terminal_rpg::data::player::Player;
+
+    let mut player = Player::default();
+
+    player.announce_level_ups();
+    ```
+    */
+    pub fn announce_level_ups(&mut self) {
+        let level_ups: Vec<LevelUp> = std::mem::take(&mut self.xp.level_ups);
+
+        for level_up in &level_ups {
+            success(Some(&level_up.to_string()));
+
+            for guild in Guild::iter().filter(|guild| guild.skill() == level_up.skill) {
+                if let Some(rank) = Guilds::promote(self, guild) {
+                    self.save();
+                    success(Some(&format!("You have been promoted to {rank} of the {guild} guild!")));
+                }
+            }
+
+            if level_up.skill != XPType::Combat {
+                continue;
+            }
+
+            for skill in classes::unlocked_at(self, level_up.level) {
+                success(Some(&format!("You unlocked the {} skill {skill}!", self.class)));
+            }
+        }
+
+        Achievements::check(self);
+    }
+
     /**
     Save player data to disk.
 
@@ -336,3 +499,170 @@ impl Player {
         Self::paginate(self);
     }
 }
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn profile_round_trip() {
+        use super::Player;
+        use crate::data::{
+            consumables::Consumable,
+            farming::Crop,
+            guilds::{Guild, Membership},
+            inventory::{loot::Gear, storage::Stored, tools::Tool},
+        };
+
+        let mut player = Player::new(&"Steve", &"hash", false);
+
+        for level in [1, 50, 500] {
+            player.loot.add(Gear::generate(level));
+        }
+
+        player.storage.stored.push(Stored::Loot(Gear::generate(1)));
+        player.storage.stored.push(Stored::Item {
+            id: "bait".to_string(),
+            quantity: 5,
+        });
+        player.storage.stored.push(Stored::Weapon {
+            id: "iron_sword".to_string(),
+            data: player.weapons.get("iron_sword").clone(),
+        });
+
+        player.farm.plots[0].crop = Some(Crop::Wheat);
+        player.tools.durability.insert(Tool::Axe, 10);
+        player
+            .guilds
+            .memberships
+            .insert(Guild::Mining, Membership::new(Guild::Mining));
+
+        player.effects.expires.insert(Consumable::Strength, 12);
+        player.statistics.kill("Giant Spider");
+        player.statistics.gather(Guild::Mining, 3);
+        player.statistics.drink(Consumable::Antidote);
+
+        let encoded = player.to_string();
+
+        let decoded = Player::try_from(encoded.clone());
+
+        assert!(decoded.is_ok(), "Player profile failed to decode.");
+        assert_eq!(decoded.unwrap_or_default().to_string(), encoded);
+    }
+
+    #[test]
+    fn reset_keeps_the_vault() {
+        use super::Player;
+        use crate::data::inventory::storage::Stored;
+
+        let mut player = Player::default();
+
+        player.bank.vault = 500;
+        player.bank.checking = 100;
+        player.storage.slots += 1;
+        player.storage.stored.push(Stored::Item {
+            id: "bait".to_string(),
+            quantity: 5,
+        });
+
+        player.reset();
+
+        assert_eq!(player.bank.vault, 500);
+        assert_eq!(player.bank.checking, 0);
+        assert_eq!(player.storage.stored.len(), 1);
+    }
+
+    /// A profile saved before the catalog, keeping one weapon and one armor.
+    const LEGACY_PROFILE: &str = r#"
+[health]
+hp = 100
+hunger = 0
+
+[xp]
+combat = 500
+fishing = 0
+cooking = 0
+woodcutting = 0
+mining = 0
+smithing = 0
+thieving = 0
+
+[achievements]
+monsters_killed = 0
+stronghold_defeated = false
+earned_million_gold = false
+level_100_reached = false
+hacked_the_game = false
+
+[bank]
+wallet = 10
+account1 = 100
+account2 = 0
+account3 = 50
+account4 = 0
+
+[guilds]
+thieving = false
+fishing = true
+cooking = false
+woodcutting = false
+mining = false
+smithing = false
+
+[equipment]
+armor = "Leather"
+weapon = "Iron"
+
+[items]
+bait = 7
+seeds = 0
+potions = 0
+
+[armor.leather]
+owns = true
+defense = 10
+durability = 100
+default_durability = 50
+equipped = true
+flag = "Leather"
+
+[weapons.iron_sword]
+owns = true
+equipped = true
+damage = 50
+durability = 200
+default_durability = 50
+flag = "Iron"
+
+[settings]
+username = "Steve"
+password_hash = "hash"
+developer = false
+hardmode = false
+"#;
+
+    #[test]
+    fn legacy_profile_migrates() {
+        use super::Player;
+        use crate::data::{catalog::Catalog, guilds::Guild};
+
+        let decoded = Player::try_from(LEGACY_PROFILE.to_string());
+
+        assert!(decoded.is_ok(), "Legacy player profile failed to decode.");
+
+        let mut player = decoded.unwrap_or_default();
+
+        assert_eq!(player.bank.checking, 150);
+        assert_eq!(player.items.quantity("bait"), 7);
+        assert!(player.guilds.check(Guild::Fishing));
+        assert!(!player.guilds.check(Guild::Thieving));
+        assert_eq!(player.equipment.weapon.as_deref(), Some("iron_sword"));
+        assert_eq!(player.equipment.chest.as_deref(), Some("leather"));
+
+        let iron_sword = Catalog::get().weapon("iron_sword").map(|weapon| weapon.name.clone());
+        let weapon = player.weapons.get("iron_sword");
+
+        assert!(weapon.owns && weapon.equipped);
+        assert_eq!(Some(weapon.name.clone()), iron_sword);
+        assert!(weapon.durability <= weapon.default_durability);
+        assert!(player.armor.get("leather").owns);
+    }
+}
@@ -0,0 +1,175 @@
+/*!
+# Quests
+
+Quests are defined in the catalog, so content packs can add their own.
+Each asks the player to defeat a number of one enemy, and pays gold,
+combat xp, and items once finished.
+
+Quests are taken from the quest log while wandering the realm. Every
+defeat of the enemy counts, wherever the battle happens. A finished quest
+can't be taken again, and quests are kept when the player dies.
+*/
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet};
+use strum::IntoEnumIterator;
+
+use crate::{
+    combat::enemy::Enemies,
+    data::{
+        catalog::{Catalog, QuestDef},
+        inventory::{
+            bank::Account,
+            ledger::{Ledger, Source},
+        },
+        player::Player,
+        xp::{XPType, XP},
+    },
+    prelude::{checkmark, csv_table, error},
+};
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
+pub struct Quests {
+    /// Enemies defeated so far for each quest taken, keyed by quest id.
+    pub active: BTreeMap<String, usize>,
+
+    /// Ids of finished quests.
+    pub completed: BTreeSet<String>,
+}
+
+impl Quests {
+    pub fn accept(player: &mut Player, id: &str) -> error::Result<()> {
+        if Catalog::get().quest(id).is_none() {
+            return Err(Box::new(error::Miscellaneous::Custom("That quest does not exist.")));
+        }
+
+        if player.quests.completed.contains(id) {
+            return Err(Box::new(error::Miscellaneous::Custom(
+                "You have already finished that quest.",
+            )));
+        }
+
+        if player.quests.active.contains_key(id) {
+            return Err(Box::new(error::Miscellaneous::Custom(
+                "You have already taken that quest.",
+            )));
+        }
+
+        player.quests.active.insert(id.to_string(), 0);
+        Ok(())
+    }
+
+    /// Count a defeated enemy toward every quest asking for it, paying out
+    /// and returning the quests it finished.
+    pub fn record_kill(player: &mut Player, enemy: &str) -> Vec<&'static QuestDef> {
+        let mut finished: Vec<&'static QuestDef> = vec![];
+
+        for (id, kills) in &mut player.quests.active {
+            let Some(quest) = Catalog::get().quest(id) else {
+                continue;
+            };
+
+            if quest.enemy == enemy {
+                *kills += 1;
+            }
+
+            if *kills >= quest.count {
+                finished.push(quest);
+            }
+        }
+
+        for quest in &finished {
+            player.quests.active.remove(&quest.id);
+            player.quests.completed.insert(quest.id.clone());
+
+            Ledger::credit(player, Source::Quests, Account::Wallet, quest.gold);
+
+            if let Err(message) = XP::add(player, XPType::Combat, quest.xp) {
+                message.print(true);
+            }
+
+            for (item, quantity) in &quest.items {
+                *player.items.get(item) += quantity;
+            }
+        }
+
+        finished
+    }
+
+    pub fn table(player: &Player) {
+        let catalog: &Catalog = Catalog::get();
+        let mut strings: Vec<String> = vec!["Quest,Enemy,Progress,Gold,XP,Items,Finished".to_string()];
+
+        for quest in &catalog.quests {
+            let kills: usize = player.quests.active.get(&quest.id).copied().unwrap_or_default();
+            let finished: bool = player.quests.completed.contains(&quest.id);
+
+            let progress: String = if finished {
+                format!("{0}/{0}", quest.count)
+            } else if player.quests.active.contains_key(&quest.id) {
+                format!("{kills}/{}", quest.count)
+            } else {
+                "Not Taken".to_string()
+            };
+
+            let items: Vec<String> = quest
+                .items
+                .iter()
+                .map(|(item, quantity)| format!("{quantity} {}", catalog.item_name(item)))
+                .collect();
+
+            strings.push(format!(
+                "{},{},{progress},{},{},{},{}",
+                quest.name,
+                enemy_name(&quest.enemy),
+                quest.gold,
+                quest.xp,
+                items.join(" + "),
+                checkmark(finished)
+            ));
+        }
+
+        csv_table(&strings);
+    }
+}
+
+/// Display name for a built-in species or content pack enemy id.
+pub fn enemy_name(id: &str) -> String {
+    if let Some(species) = Enemies::iter().find(|species| species.id() == id) {
+        return species.to_string();
+    }
+
+    Catalog::get()
+        .enemy(id)
+        .map_or_else(|| id.to_string(), |enemy| enemy.name.clone())
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn finished_quest_pays_once() {
+        use super::Quests;
+        use crate::data::player::Player;
+
+        let mut player = Player::default();
+        let wallet: usize = player.bank.wallet;
+
+        assert!(Quests::accept(&mut player, "wolf_cull").is_ok());
+        assert!(Quests::accept(&mut player, "wolf_cull").is_err());
+
+        for _ in 0..4 {
+            assert!(Quests::record_kill(&mut player, "dire_wolf").is_empty());
+        }
+
+        assert!(Quests::record_kill(&mut player, "goblin").is_empty());
+        assert_eq!(player.quests.active.get("wolf_cull"), Some(&4));
+
+        let finished = Quests::record_kill(&mut player, "dire_wolf");
+
+        assert_eq!(finished.len(), 1);
+        assert_eq!(player.bank.wallet, wallet + finished[0].gold);
+        assert_eq!(player.items.quantity("furs"), 5);
+        assert!(player.quests.completed.contains("wolf_cull"));
+        assert!(Quests::record_kill(&mut player, "dire_wolf").is_empty());
+        assert!(Quests::accept(&mut player, "wolf_cull").is_err());
+    }
+}
@@ -1,5 +1,8 @@
 use crate::prelude::{generate_hash, player_file_path, success};
-use crate::{data::player::Player, panic_menu};
+use crate::{
+    data::{achievements::Achievements, player::Player},
+    panic_menu,
+};
 use serde::{Deserialize, Serialize};
 use std::{fmt::Display, fs, path::Path};
 
@@ -44,6 +47,7 @@ impl Settings {
         if player.settings.developer {
             player.achievements.hacked_the_game = true;
             success(Some("Developer mode enabled."));
+            Achievements::check(player);
         } else {
             success(Some("Developer mode disabled."));
         }
@@ -0,0 +1,186 @@
+/*!
+# Smithing
+
+Upgrades owned weapons and armor from +1 to +10 using materials gathered
+in the guilds. Each upgrade level adds 10% of the base damage/defense.
+
+Smiths can also repair their own equipment with ingots, which is cheaper
+than paying the shops.
+
+# Materials
+
+- Ingots: Every upgrade level
+- Rubies: +4 and above
+- Dragon Hides: +8 and above
+*/
+use crate::{
+    data::{
+        inventory::items,
+        player::Player,
+        xp::{XPType, XP},
+    },
+    prelude::{error, random_num},
+};
+
+/// Highest upgrade level a piece of equipment can reach.
+pub const MAX_UPGRADE: usize = 10;
+
+/// A piece of equipment that can be taken to the smithy.
+pub enum Gear {
+    Weapon(String),
+    Armor(String),
+}
+
+/// Materials required to upgrade equipment to the target level.
+pub fn materials(target: usize) -> Vec<(items::Types, usize)> {
+    let mut materials: Vec<(items::Types, usize)> = vec![(items::Types::Ingots, target * 5)];
+
+    if target >= 4 {
+        materials.push((items::Types::Rubies, target - 3));
+    }
+
+    if target >= 8 {
+        materials.push((items::Types::DragonHides, target - 7));
+    }
+
+    materials
+}
+
+/// Percent chance an upgrade to the target level succeeds. Higher smithing
+/// levels make upgrades more reliable, capped at 95%.
+pub fn success_chance(target: usize, smithing_level: usize) -> usize {
+    let base: usize = 100_usize.saturating_sub(target * 8);
+    let chance: usize = base + smithing_level / 2;
+
+    chance.clamp(5, 95)
+}
+
+/// Percent chance the player's upgrade to the target level succeeds. Each
+/// point of intelligence counts as two smithing levels.
+pub fn player_success_chance(player: &Player, target: usize) -> usize {
+    success_chance(
+        target,
+        XP::get_level(player.xp.smithing) + player.attributes.intelligence * 2,
+    )
+}
+
+/// Current upgrade level of the gear, or an error if the player doesn't own it.
+pub fn upgrade_level(player: &mut Player, gear: &Gear) -> error::Result<usize> {
+    let (owns, upgrade) = match gear {
+        Gear::Weapon(id) => {
+            let weapon = player.weapons.get(id);
+            (weapon.owns, weapon.upgrade)
+        }
+        Gear::Armor(id) => {
+            let armor = player.armor.get(id);
+            (armor.owns, armor.upgrade)
+        }
+    };
+
+    if !owns {
+        return Err(Box::new(error::Inventory::ItemNotOwned));
+    }
+
+    if upgrade >= MAX_UPGRADE {
+        return Err(Box::new(error::Inventory::MaxUpgrade));
+    }
+
+    Ok(upgrade)
+}
+
+/**
+Attempt to upgrade a piece of gear. Materials are consumed whether or not
+the upgrade succeeds. Returns true if the upgrade succeeded.
+*/
+pub fn upgrade(player: &mut Player, gear: &Gear) -> error::Result<bool> {
+    let target: usize = upgrade_level(player, gear)? + 1;
+    let required = materials(target);
+
+    for (flag, quantity) in &required {
+        if *player.items.get(*flag) < *quantity {
+            return Err(Box::new(error::Inventory::NotEnoughItem(flag.to_string())));
+        }
+    }
+
+    for (flag, quantity) in &required {
+        *player.items.get(*flag) -= quantity;
+    }
+
+    XP::add(player, XPType::Smithing, target * 5)?;
+
+    let chance: usize = player_success_chance(player, target);
+
+    if random_num(1, 100) > chance {
+        return Ok(false);
+    }
+
+    match gear {
+        Gear::Weapon(id) => player.weapons.get(id).upgrade = target,
+        Gear::Armor(id) => player.armor.get(id).upgrade = target,
+    }
+
+    Ok(true)
+}
+
+/// Ingots needed to repair equipment, one for every 50 durability lost.
+pub fn repair_materials(missing_durability: usize) -> usize {
+    missing_durability.div_ceil(50)
+}
+
+/// Durability the gear has lost, or an error if it can't be repaired.
+pub fn missing_durability(player: &mut Player, gear: &Gear) -> error::Result<usize> {
+    let (owns, missing) = match gear {
+        Gear::Weapon(id) => {
+            let weapon = player.weapons.get(id);
+            (weapon.owns, weapon.missing_durability())
+        }
+        Gear::Armor(id) => {
+            let armor = player.armor.get(id);
+            (armor.owns, armor.missing_durability())
+        }
+    };
+
+    if !owns {
+        return Err(Box::new(error::Inventory::ItemNotOwned));
+    }
+
+    if missing == 0 {
+        return Err(Box::new(error::Inventory::NothingToRepair));
+    }
+
+    Ok(missing)
+}
+
+/// Fully repair a piece of gear using ingots.
+pub fn repair(player: &mut Player, gear: &Gear) -> error::Result<()> {
+    let ingots: usize = repair_materials(missing_durability(player, gear)?);
+
+    if *player.items.get(items::Types::Ingots) < ingots {
+        return Err(Box::new(error::Inventory::NotEnoughItem(
+            items::Types::Ingots.to_string(),
+        )));
+    }
+
+    *player.items.get(items::Types::Ingots) -= ingots;
+    XP::add(player, XPType::Smithing, ingots)?;
+
+    match gear {
+        Gear::Weapon(id) => player.weapons.get(id).repair(),
+        Gear::Armor(id) => player.armor.get(id).repair(),
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn success_chance_bounds() {
+        use super::{success_chance, MAX_UPGRADE};
+
+        assert_eq!(success_chance(1, 1), 92);
+        assert_eq!(success_chance(1, 1_000), 95);
+        assert_eq!(success_chance(MAX_UPGRADE, 1), 20);
+        assert!(success_chance(MAX_UPGRADE, 1) < success_chance(MAX_UPGRADE, 50));
+    }
+}
@@ -0,0 +1,214 @@
+/*!
+# Statistics
+
+Lifetime statistics for the profile. Unlike achievements, statistics are
+kept when the player dies and are only cleared when the whole profile is
+reset.
+
+# Tracked
+
+- Kills per enemy species
+- Damage dealt and taken
+- Potions used
+- Gold earned and spent
+- Items gathered per guild
+- Deaths and retreats
+- Stronghold floors cleared
+- Time played
+*/
+use serde::{Deserialize, Serialize};
+use std::{collections::BTreeMap, time::Instant};
+
+use crate::{
+    data::{
+        consumables::Consumable,
+        guilds::Guild,
+        inventory::{bank::Account, ledger::Source},
+    },
+    prelude::csv_table,
+};
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+pub struct Statistics {
+    /// Kills keyed by enemy name, so enemies from content packs are counted too.
+    pub kills: BTreeMap<String, usize>,
+    pub damage_dealt: usize,
+    pub damage_taken: usize,
+    pub potions_used: BTreeMap<Consumable, usize>,
+    pub gold_earned: usize,
+    pub gold_spent: usize,
+    pub items_gathered: BTreeMap<Guild, usize>,
+    pub deaths: usize,
+    pub retreats: usize,
+    pub stronghold_floors: usize,
+    pub deepest_floor: usize,
+
+    /// Real seconds spent playing.
+    pub seconds_played: u64,
+
+    /// When time played was last counted this session.
+    #[serde(skip)]
+    pub last_tick: Option<Instant>,
+}
+
+impl Statistics {
+    pub fn reset(&mut self) {
+        *self = Self::default();
+    }
+
+    pub fn kill(&mut self, enemy: &str) {
+        *self.kills.entry(enemy.to_string()).or_insert(0) += 1;
+    }
+
+    pub fn gather(&mut self, guild: Guild, quantity: usize) {
+        *self.items_gathered.entry(guild).or_insert(0) += quantity;
+    }
+
+    pub fn drink(&mut self, consumable: Consumable) {
+        *self.potions_used.entry(consumable).or_insert(0) += 1;
+    }
+
+    /// Count gold coming into or leaving the player's accounts. Moving gold
+    /// between accounts, borrowing and repaying loans, and gold lost on death
+    /// are not counted. Interest charged on a loan counts as spent.
+    pub fn record_gold(&mut self, source: Source, account: Account, amount: isize) {
+        if account == Account::Loan {
+            if source == Source::Interest {
+                self.gold_spent += amount.unsigned_abs();
+            }
+
+            return;
+        }
+
+        if matches!(
+            source,
+            Source::Deposit | Source::Withdrawal | Source::Loan | Source::Death
+        ) {
+            return;
+        }
+
+        if amount > 0 {
+            self.gold_earned += amount.unsigned_abs();
+        } else {
+            self.gold_spent += amount.unsigned_abs();
+        }
+    }
+
+    pub fn clear_floor(&mut self, floor: usize) {
+        self.stronghold_floors += 1;
+        self.deepest_floor = self.deepest_floor.max(floor);
+    }
+
+    /// Add the time since the last tick to the time played.
+    pub fn tick(&mut self) {
+        let now: Instant = Instant::now();
+
+        if let Some(last) = self.last_tick {
+            self.seconds_played += now.duration_since(last).as_secs();
+        }
+
+        self.last_tick = Some(now);
+    }
+
+    pub fn time_played(&self) -> String {
+        let minutes: u64 = self.seconds_played / 60;
+
+        format!("{}h {:02}m", minutes / 60, minutes % 60)
+    }
+
+    pub fn table(&self) {
+        csv_table(&[
+            "Statistic,Value".to_string(),
+            format!("Total Kills,{}", self.kills.values().sum::<usize>()),
+            format!("Damage Dealt,{}", self.damage_dealt),
+            format!("Damage Taken,{}", self.damage_taken),
+            format!("Potions Used,{}", self.potions_used.values().sum::<usize>()),
+            format!("Gold Earned,{}", self.gold_earned),
+            format!("Gold Spent,{}", self.gold_spent),
+            format!("Deaths,{}", self.deaths),
+            format!("Retreats,{}", self.retreats),
+            format!("Stronghold Floors Cleared,{}", self.stronghold_floors),
+            format!("Deepest Stronghold Floor,{}", self.deepest_floor),
+            format!("Time Played,{}", self.time_played()),
+        ]);
+    }
+
+    pub fn kills_table(&self) {
+        let mut strings: Vec<String> = vec!["Enemy,Kills".to_string()];
+
+        for (enemy, kills) in &self.kills {
+            strings.push(format!("{enemy},{kills}"));
+        }
+
+        csv_table(&strings);
+    }
+
+    pub fn gathering_table(&self) {
+        let mut strings: Vec<String> = vec!["Guild,Items Gathered".to_string()];
+
+        for (guild, quantity) in &self.items_gathered {
+            strings.push(format!("{guild},{quantity}"));
+        }
+
+        csv_table(&strings);
+    }
+
+    pub fn potions_table(&self) {
+        let mut strings: Vec<String> = vec!["Potion,Used".to_string()];
+
+        for (consumable, used) in &self.potions_used {
+            strings.push(format!("{consumable},{used}"));
+        }
+
+        csv_table(&strings);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn gold_transfers_are_not_counted() {
+        use super::Statistics;
+        use crate::data::inventory::{bank::Account, ledger::Source};
+
+        let mut statistics = Statistics::default();
+
+        statistics.record_gold(Source::Battle, Account::Wallet, 50);
+        statistics.record_gold(Source::ArmorShop, Account::Wallet, -20);
+        statistics.record_gold(Source::Deposit, Account::Wallet, -30);
+        statistics.record_gold(Source::Deposit, Account::Checking, 30);
+
+        assert_eq!(statistics.gold_earned, 50);
+        assert_eq!(statistics.gold_spent, 20);
+    }
+
+    #[test]
+    fn loan_interest_is_spent() {
+        use super::Statistics;
+        use crate::data::inventory::{bank::Account, ledger::Source};
+
+        let mut statistics = Statistics::default();
+
+        statistics.record_gold(Source::Loan, Account::Loan, 100);
+        statistics.record_gold(Source::Loan, Account::Wallet, 100);
+        statistics.record_gold(Source::Interest, Account::Loan, 5);
+        statistics.record_gold(Source::Interest, Account::Savings, 3);
+        statistics.record_gold(Source::Loan, Account::Wallet, -50);
+        statistics.record_gold(Source::Loan, Account::Loan, -50);
+
+        assert_eq!(statistics.gold_earned, 3);
+        assert_eq!(statistics.gold_spent, 5);
+    }
+
+    #[test]
+    fn vault_slots_are_spent() {
+        use super::Statistics;
+        use crate::data::inventory::{bank::Account, ledger::Source};
+
+        let mut statistics = Statistics::default();
+
+        statistics.record_gold(Source::Storage, Account::Wallet, -250);
+
+        assert_eq!(statistics.gold_spent, 250);
+    }
+}
@@ -0,0 +1,288 @@
+/*!
+# Thieving
+
+Members of the Thieving guild pick pockets and burgle homes for gold and
+valuables. Harder targets need a higher thieving level and pay more, but
+getting caught is costly: a fine, time in jail, or a fight with the guards.
+
+Every crime the player is caught for adds to their bounty. Until the bounty
+is paid off, shopkeepers recognize the player and raise their prices: shop
+goods, tools, shop repairs, and guild memberships all cost more. Smithing
+upgrades and repairs are paid in materials rather than gold, so they are not
+affected, and neither are sale prices or contract rewards.
+
+# Targets
+
+- Pickpocket a Peasant: level 1
+- Pickpocket a Merchant: level 5
+- Burgle a House: level 10
+- Burgle a Manor: level 25
+*/
+use crate::{
+    data::{
+        classes,
+        guilds::Guild,
+        health::WORK_HUNGER,
+        inventory::{
+            bank::Account,
+            items,
+            ledger::{Ledger, Source},
+        },
+        player::Player,
+        xp::{XPType, XP},
+    },
+    prelude::{error, random_num},
+};
+use std::fmt::Display;
+use strum_macros::EnumIter;
+
+/// Highest shop price increase from a bounty, as a percentage.
+pub const MAX_MARKUP: usize = 50;
+
+/// Bounty gold that raises shop prices by 1%.
+pub const BOUNTY_PER_PERCENT: usize = 10;
+
+#[derive(Clone, Copy, Debug, PartialEq, EnumIter)]
+pub enum Target {
+    Peasant,
+    Merchant,
+    House,
+    Manor,
+}
+
+impl Display for Target {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Target::Peasant => write!(f, "Pickpocket a Peasant"),
+            Target::Merchant => write!(f, "Pickpocket a Merchant"),
+            Target::House => write!(f, "Burgle a House"),
+            Target::Manor => write!(f, "Burgle a Manor"),
+        }
+    }
+}
+
+/// What happens to a thief who gets caught.
+pub enum Caught {
+    Fined(usize),
+    Jailed(usize),
+
+    /// The guards arrive, and the thief must fight their way out.
+    Guards,
+}
+
+/// How a theft ended.
+pub enum Theft {
+    Success {
+        gold: usize,
+        valuable: Option<items::Types>,
+        spotted: bool,
+    },
+    Caught(Caught),
+}
+
+impl Target {
+    /// Thieving level needed to attempt the target.
+    pub fn level(self) -> usize {
+        match self {
+            Target::Peasant => 1,
+            Target::Merchant => 5,
+            Target::House => 10,
+            Target::Manor => 25,
+        }
+    }
+
+    /// Smallest and largest gold haul.
+    pub fn loot(self) -> (usize, usize) {
+        match self {
+            Target::Peasant => (1, 5),
+            Target::Merchant => (5, 20),
+            Target::House => (20, 60),
+            Target::Manor => (75, 200),
+        }
+    }
+
+    /// Bounty added when caught.
+    pub fn bounty(self) -> usize {
+        match self {
+            Target::Peasant => 10,
+            Target::Merchant => 25,
+            Target::House => 75,
+            Target::Manor => 200,
+        }
+    }
+
+    /// Percent chance of success, rising with levels above the target's, between 10% and 95%.
+    pub fn success_chance(self, thieving_level: usize) -> usize {
+        let base: usize = match self {
+            Target::Peasant | Target::House => 70,
+            Target::Merchant => 60,
+            Target::Manor => 50,
+        };
+
+        (base + thieving_level.saturating_sub(self.level()) * 2).clamp(10, 95)
+    }
+
+    /// Burglaries are more likely to bring the guards.
+    fn punishment(self) -> Caught {
+        let guards: usize = match self {
+            Target::Peasant | Target::Merchant => 15,
+            Target::House | Target::Manor => 40,
+        };
+
+        let roll: usize = random_num(1, 100);
+
+        if roll <= guards {
+            Caught::Guards
+        } else if roll <= guards + (100 - guards) / 2 {
+            Caught::Fined(self.bounty() * 2)
+        } else {
+            Caught::Jailed(self.level().clamp(4, 24))
+        }
+    }
+}
+
+/// Percent shop prices are raised by the player's bounty.
+pub fn markup_percent(player: &Player) -> usize {
+    (player.bounty / BOUNTY_PER_PERCENT).min(MAX_MARKUP)
+}
+
+/// A shop price raised by the player's bounty.
+pub fn markup(player: &Player, price: usize) -> usize {
+    price + price * markup_percent(player) / 100
+}
+
+/// Percent chance the player's theft succeeds, raised by class passives up to 95%.
+pub fn success_chance(player: &Player, target: Target) -> usize {
+    let thieving_level: usize = XP::get_level(player.xp.thieving);
+
+    (target.success_chance(thieving_level) + classes::bonus_thieving_chance(player)).min(95)
+}
+
+/// Attempt a theft, which takes an hour. Fines and jail time are applied here;
+/// a fight with the guards is left to the caller.
+pub fn steal(player: &mut Player, target: Target) -> error::Result<Theft> {
+    let thieving_level: usize = XP::get_level(player.xp.thieving);
+
+    if thieving_level < target.level() {
+        return Err(Box::new(error::Inventory::NotEnoughXP));
+    }
+
+    player.pass_time(1);
+    player.health.hunger_up(WORK_HUNGER);
+
+    if random_num(1, 100) > success_chance(player, target) {
+        player.bounty += target.bounty();
+        XP::add(player, XPType::Thieving, 1)?;
+
+        let caught: Caught = target.punishment();
+
+        match caught {
+            Caught::Fined(fine) => {
+                let paid: usize = fine.min(player.bank.wallet);
+
+                Ledger::debit(player, Source::Fines, Account::Wallet, paid);
+
+                // Whatever can't be paid is served in jail
+                if paid < fine {
+                    player.pass_time(target.level().clamp(4, 24));
+                }
+            }
+            Caught::Jailed(hours) => player.pass_time(hours),
+            Caught::Guards => {}
+        }
+
+        return Ok(Theft::Caught(caught));
+    }
+
+    let (min, max) = target.loot();
+    let gold: usize = classes::stolen_gold(player, random_num(min, max));
+
+    Ledger::credit(player, Source::Thieving, Account::Wallet, gold);
+
+    let valuable: Option<items::Types> = match target {
+        Target::House if random_num(1, 10) == 1 => Some(items::Types::MagicScrolls),
+        Target::Manor if random_num(1, 5) == 1 => Some(items::Types::Rubies),
+        _ => None,
+    };
+
+    if let Some(item) = valuable {
+        *player.items.get(item) += 1;
+        player.statistics.gather(Guild::Thieving, 1);
+    }
+
+    // Even a successful thief is sometimes recognized
+    let spotted: bool = random_num(1, 10) == 1;
+
+    if spotted {
+        player.bounty += target.bounty() / 2;
+    }
+
+    XP::add(player, XPType::Thieving, target.level() + random_num(1, 4))?;
+
+    Ok(Theft::Success {
+        gold,
+        valuable,
+        spotted,
+    })
+}
+
+/// Pay off as much of the bounty as the wallet allows, returning the amount paid.
+pub fn pay_bounty(player: &mut Player) -> error::Result<usize> {
+    if player.bounty == 0 {
+        return Err(Box::new(error::Miscellaneous::Custom("You do not have a bounty.")));
+    }
+
+    let paid: usize = player.bounty.min(player.bank.wallet);
+
+    if paid == 0 {
+        return Err(Box::new(error::Inventory::NotEnoughGold));
+    }
+
+    Ledger::debit(player, Source::Fines, Account::Wallet, paid);
+    player.bounty -= paid;
+
+    Ok(paid)
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn markup_is_capped() {
+        use super::{markup, BOUNTY_PER_PERCENT, MAX_MARKUP};
+        use crate::data::player::Player;
+
+        let mut player = Player::default();
+
+        assert_eq!(markup(&player, 100), 100);
+
+        player.bounty = BOUNTY_PER_PERCENT * 20;
+        assert_eq!(markup(&player, 100), 120);
+
+        player.bounty = BOUNTY_PER_PERCENT * MAX_MARKUP * 10;
+        assert_eq!(markup(&player, 100), 100 + MAX_MARKUP);
+    }
+
+    #[test]
+    fn bounty_is_paid_as_far_as_the_wallet_allows() {
+        use super::pay_bounty;
+        use crate::data::player::Player;
+
+        let mut player = Player::default();
+
+        assert!(pay_bounty(&mut player).is_err());
+
+        player.bounty = 100;
+        player.bank.wallet = 0;
+        assert!(pay_bounty(&mut player).is_err());
+
+        player.bank.wallet = 40;
+        assert_eq!(pay_bounty(&mut player).ok(), Some(40));
+        assert_eq!(player.bounty, 60);
+        assert_eq!(player.bank.wallet, 0);
+
+        player.bank.wallet = 100;
+        assert_eq!(pay_bounty(&mut player).ok(), Some(60));
+        assert_eq!(player.bounty, 0);
+        assert_eq!(player.bank.wallet, 40);
+    }
+}
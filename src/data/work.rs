@@ -0,0 +1,214 @@
+/*!
+# Guild Work
+
+What a shift of work at each guild needs and produces. Work can require
+input items that are used up whether or not the shift succeeds, and a tool
+that wears down with every shift. Every shift makes the player hungrier.
+
+Higher guild levels make each shift more likely to succeed, yield more, and
+turn up rare finds more often. Some class passives raise the yield further.
+
+# Guilds
+
+- Fishing: Bait and a fishing rod for Fish, sometimes Rubies
+- Cooking: Fish for Cooked Fish
+- Woodcutting: An axe for Wood, sometimes Seeds
+- Mining: A pickaxe for Ore, sometimes Rubies
+- Smithing: Ore for Ingots
+*/
+use crate::{
+    data::{
+        classes,
+        guilds::Guild,
+        health::WORK_HUNGER,
+        inventory::{items, tools::Tool},
+        player::Player,
+        xp::{XPType, XP},
+    },
+    prelude::{error, random_num},
+};
+
+pub struct Work {
+    pub guild: Guild,
+    pub skill: XPType,
+    pub tool: Option<Tool>,
+    pub inputs: &'static [(items::Types, usize)],
+    pub produce: items::Types,
+    pub rare: Option<items::Types>,
+}
+
+/// What came of a shift of work.
+pub enum Shift {
+    Success {
+        produced: usize,
+        rare: Option<items::Types>,
+        tool_broke: bool,
+    },
+    Failed {
+        tool_broke: bool,
+    },
+}
+
+impl Work {
+    /// Work done at a guild, or None for guilds with their own menus.
+    pub fn of(guild: Guild) -> Option<Self> {
+        let work = match guild {
+            Guild::Fishing => Self {
+                guild,
+                skill: XPType::Fishing,
+                tool: Some(Tool::Rod),
+                inputs: &[(items::Types::Bait, 1)],
+                produce: items::Types::Fish,
+                rare: Some(items::Types::Rubies),
+            },
+            Guild::Cooking => Self {
+                guild,
+                skill: XPType::Cooking,
+                tool: None,
+                inputs: &[(items::Types::Fish, 1)],
+                produce: items::Types::CookedFish,
+                rare: None,
+            },
+            Guild::Woodcutting => Self {
+                guild,
+                skill: XPType::Woodcutting,
+                tool: Some(Tool::Axe),
+                inputs: &[],
+                produce: items::Types::Wood,
+                rare: Some(items::Types::Seeds),
+            },
+            Guild::Mining => Self {
+                guild,
+                skill: XPType::Mining,
+                tool: Some(Tool::Pickaxe),
+                inputs: &[],
+                produce: items::Types::Ore,
+                rare: Some(items::Types::Rubies),
+            },
+            Guild::Smithing => Self {
+                guild,
+                skill: XPType::Smithing,
+                tool: None,
+                inputs: &[(items::Types::Ore, 1)],
+                produce: items::Types::Ingots,
+                rare: None,
+            },
+            Guild::Thieving | Guild::Farming | Guild::Hunting => return None,
+        };
+
+        Some(work)
+    }
+
+    /// Percent chance a shift succeeds, capped at 98%.
+    pub fn success_chance(level: usize) -> usize {
+        (60 + level * 2).min(98)
+    }
+
+    /// Most a single shift can yield.
+    pub fn max_yield(level: usize) -> usize {
+        1 + level / 10
+    }
+
+    /// Percent chance of a rare find on a successful shift, capped at 10%.
+    pub fn rare_chance(level: usize) -> usize {
+        (1 + level / 20).min(10)
+    }
+
+    /// Checks the player has the tool and inputs for a shift.
+    pub fn check(&self, player: &Player) -> error::Result<()> {
+        if let Some(tool) = self.tool {
+            if !player.tools.owns(tool) {
+                return Err(Box::new(error::Miscellaneous::Custom(
+                    "You need the right tool. Buy one from the Trading Post.",
+                )));
+            }
+        }
+
+        for (item, quantity) in self.inputs {
+            if player.items.quantity(item) < *quantity {
+                return Err(Box::new(error::Inventory::NotEnoughItem(item.to_string())));
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Work a shift, which takes an hour.
+    pub fn shift(&self, player: &mut Player) -> error::Result<Shift> {
+        self.check(player)?;
+
+        let level: usize = XP::get_level(*player.xp.get(self.skill));
+
+        for (item, quantity) in self.inputs {
+            *player.items.get(item) -= quantity;
+        }
+
+        let tool_broke: bool = self.tool.is_some_and(|tool| player.tools.wear(tool));
+        player.pass_time(1);
+        player.health.hunger_up(WORK_HUNGER);
+
+        if random_num(1, 100) > Self::success_chance(level) {
+            XP::add(player, self.skill, 1)?;
+            return Ok(Shift::Failed { tool_broke });
+        }
+
+        let produced: usize = random_num(1, Self::max_yield(level)) + classes::bonus_yield(player, self.skill);
+
+        *player.items.get(self.produce) += produced;
+
+        let rare: Option<items::Types> = self.rare.filter(|_| random_num(1, 100) <= Self::rare_chance(level));
+
+        if let Some(item) = rare {
+            *player.items.get(item) += 1;
+        }
+
+        player
+            .statistics
+            .gather(self.guild, produced + usize::from(rare.is_some()));
+
+        for _ in 0..produced {
+            XP::increment(player, self.skill);
+        }
+
+        Ok(Shift::Success {
+            produced,
+            rare,
+            tool_broke,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn shifts_use_inputs_and_wear_tools() {
+        use super::Work;
+        use crate::data::{
+            guilds::Guild,
+            inventory::{items, tools::Tool},
+            player::Player,
+        };
+
+        let mut player = Player::default();
+        let fishing = Work::of(Guild::Fishing).expect("Fishing should have work.");
+
+        *player.items.get(items::Types::Bait) += 2;
+        assert!(fishing.shift(&mut player).is_err());
+        assert_eq!(player.items.quantity(items::Types::Bait.id()), 2);
+
+        player.tools.durability.insert(Tool::Rod, Tool::Rod.durability());
+        assert!(fishing.shift(&mut player).is_ok());
+        assert_eq!(player.items.quantity(items::Types::Bait.id()), 1);
+        assert_eq!(
+            player.tools.durability.get(&Tool::Rod),
+            Some(&(Tool::Rod.durability() - 1))
+        );
+
+        *player.items.get(items::Types::Bait) = 0;
+        assert!(fishing.shift(&mut player).is_err());
+        assert_eq!(
+            player.tools.durability.get(&Tool::Rod),
+            Some(&(Tool::Rod.durability() - 1))
+        );
+    }
+}
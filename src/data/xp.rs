@@ -1,7 +1,98 @@
+/*!
+# XP and Levels
+
+Every skill earns xp separately. Levels follow a curve set in the item
+catalog: each level needs a percentage more xp than the one before, up to a
+level cap. Xp keeps adding up past the cap, but the level stops rising.
+
+Gaining a combat level earns stat points to spend on attributes. Every
+level gained queues a level-up message, which menus show with `Player::announce_level_ups`.
+*/
 use rand::Rng;
 use serde::{Deserialize, Serialize};
+use std::fmt::Display;
+
+use crate::{
+    data::{catalog::Catalog, consumables::Consumable, player::Player},
+    prelude::{csv_table, error},
+};
+
+/// Highest percent growth the catalog may set, so each level needs at most double the last.
+pub const MAX_GROWTH: usize = 100;
+
+/// Highest level cap the catalog may set, which bounds the work of finding a level.
+pub const MAX_CAP: usize = 1_000;
+
+/// How much xp each level needs, configured in the `[levels]` section of the catalog.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct LevelCurve {
+    /// Xp needed to go from level 1 to level 2.
+    pub base: usize,
+
+    /// Percent more xp each level needs than the one before.
+    pub growth: usize,
+    pub cap: usize,
+}
+
+impl Default for LevelCurve {
+    fn default() -> Self {
+        Self {
+            base: 100,
+            growth: 4,
+            cap: 100,
+        }
+    }
+}
+
+impl LevelCurve {
+    /// Xp needed to go from the level to the next.
+    fn step(&self, level: usize) -> usize {
+        let mut step: usize = self.base;
+
+        for _ in 1..level {
+            step = step.saturating_add(step.saturating_mul(self.growth) / 100);
+        }
+
+        step
+    }
+
+    /// Total xp needed to reach a level.
+    pub fn xp_for(&self, level: usize) -> usize {
+        (1..level.min(self.cap)).fold(0, |total: usize, level| total.saturating_add(self.step(level)))
+    }
+
+    pub fn level(&self, xp: usize) -> usize {
+        let mut level: usize = 1;
+        let mut needed: usize = self.base;
+        let mut total: usize = 0;
+
+        while level < self.cap && xp >= total.saturating_add(needed) {
+            total = total.saturating_add(needed);
+            needed = needed.saturating_add(needed.saturating_mul(self.growth) / 100);
+            level += 1;
+        }
+
+        level
+    }
+}
+
+/// A level gained in a skill, waiting to be announced.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct LevelUp {
+    pub skill: XPType,
+    pub level: usize,
+}
+
+impl Display for LevelUp {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Your {} level is now {}!", self.skill, self.level)?;
 
-use crate::prelude::{csv_table, error};
+        match self.skill.stat_points() {
+            0 => Ok(()),
+            points => write!(f, " You earned {points} stat points."),
+        }
+    }
+}
 
 #[derive(Serialize, Deserialize, Debug, Clone, Default)]
 pub struct XP {
@@ -12,9 +103,20 @@ pub struct XP {
     pub mining: usize,
     pub smithing: usize,
     pub thieving: usize,
+    #[serde(default)]
+    pub farming: usize,
+    #[serde(default)]
+    pub hunting: usize,
+
+    /// Stat points earned by leveling up and not yet spent.
+    #[serde(default)]
+    pub stat_points: usize,
+
+    #[serde(skip)]
+    pub level_ups: Vec<LevelUp>,
 }
 
-#[derive(Clone, Copy)]
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
 pub enum XPType {
     Combat,
     Fishing,
@@ -23,6 +125,24 @@ pub enum XPType {
     Mining,
     Smithing,
     Thieving,
+    Farming,
+    Hunting,
+}
+
+impl std::fmt::Display for XPType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{self:?}")
+    }
+}
+
+impl XPType {
+    /// Stat points earned for each level gained in the skill. Only combat earns them.
+    pub fn stat_points(self) -> usize {
+        match self {
+            XPType::Combat => 3,
+            _ => 0,
+        }
+    }
 }
 
 impl XP {
@@ -32,11 +152,19 @@ impl XP {
 
     pub fn table(&self) {
         fn entry(name: &str, xp: usize) -> String {
-            format!("{},{},{}", name, xp, self::XP::get_level(xp))
+            let curve: &LevelCurve = &Catalog::get().levels;
+            let level: usize = self::XP::get_level(xp);
+            let next: String = if level >= curve.cap {
+                "Max".to_string()
+            } else {
+                curve.xp_for(level + 1).saturating_sub(xp).to_string()
+            };
+
+            format!("{name},{xp},{level},{next}")
         }
 
         csv_table(&[
-            "Category,XP,Level".to_string(),
+            "Category,XP,Level,XP to Next Level".to_string(),
             entry("Combat", self.combat),
             entry("Fishing", self.fishing),
             entry("Cooking", self.cooking),
@@ -44,30 +172,63 @@ impl XP {
             entry("Mining", self.mining),
             entry("Smithing", self.smithing),
             entry("Thieving", self.thieving),
+            entry("Farming", self.farming),
+            entry("Hunting", self.hunting),
             entry("Player Total", self.total()),
         ]);
     }
 
     pub fn get_level(xp: usize) -> usize {
-        (xp / 100) + 1
+        Catalog::get().levels.level(xp)
     }
 
     pub fn total(&self) -> usize {
-        self.combat + self.fishing + self.cooking + self.woodcutting + self.mining + self.smithing + self.thieving
+        self.combat
+            + self.fishing
+            + self.cooking
+            + self.woodcutting
+            + self.mining
+            + self.smithing
+            + self.thieving
+            + self.farming
+            + self.hunting
     }
 
-    pub fn increment(&mut self, flag: XPType) {
+    pub fn increment(player: &mut Player, flag: XPType) {
         let more_xp = rand::thread_rng().gen_range(1..5);
-        let xp = self.get(flag);
 
-        *xp += more_xp;
+        Self::gain(player, flag, more_xp);
+    }
+
+    pub fn add(player: &mut Player, flag: XPType, amount: usize) -> error::Result<()> {
+        Self::gain(player, flag, amount);
+        Ok(())
+    }
+
+    /// Add xp, raised by half while an Elixir of Learning is active, queueing
+    /// a level-up and granting stat points for every level gained.
+    fn gain(player: &mut Player, flag: XPType, amount: usize) {
+        let amount: usize = if player.effects.is_active(Consumable::XPBoost, player.clock.hours) {
+            (amount * 3).div_ceil(2)
+        } else {
+            amount
+        };
+
+        player.xp.level_up(flag, amount);
     }
 
-    pub fn add(&mut self, flag: XPType, amount: usize) -> error::Result<()> {
+    fn level_up(&mut self, flag: XPType, amount: usize) {
         let xp = self.get(flag);
+        let before: usize = Self::get_level(*xp);
 
-        *xp += amount;
-        Ok(())
+        *xp = xp.saturating_add(amount);
+
+        let after: usize = Self::get_level(*xp);
+
+        for level in before + 1..=after {
+            self.stat_points += flag.stat_points();
+            self.level_ups.push(LevelUp { skill: flag, level });
+        }
     }
 
     pub fn subtract(&mut self, flag: XPType, amount: usize) -> error::Result<()> {
@@ -90,6 +251,43 @@ impl XP {
             XPType::Mining => &mut self.mining,
             XPType::Smithing => &mut self.smithing,
             XPType::Thieving => &mut self.thieving,
+            XPType::Farming => &mut self.farming,
+            XPType::Hunting => &mut self.hunting,
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn level_curve_bounds() {
+        use super::LevelCurve;
+
+        let curve = LevelCurve::default();
+
+        assert_eq!(curve.level(0), 1);
+        assert_eq!(curve.level(curve.base - 1), 1);
+        assert_eq!(curve.level(curve.base), 2);
+        assert_eq!(curve.level(usize::MAX), curve.cap);
+
+        for level in 2..curve.cap {
+            assert_eq!(curve.level(curve.xp_for(level)), level);
+            assert_eq!(curve.level(curve.xp_for(level) - 1), level - 1);
+        }
+    }
+
+    #[test]
+    fn steep_curves_saturate() {
+        use super::{LevelCurve, MAX_CAP, MAX_GROWTH};
+
+        let curve = LevelCurve {
+            base: usize::MAX / 2,
+            growth: MAX_GROWTH,
+            cap: MAX_CAP,
+        };
+
+        assert_eq!(curve.xp_for(MAX_CAP), usize::MAX);
+        assert_eq!(curve.level(usize::MAX - 1), 2);
+        assert_eq!(curve.level(usize::MAX), MAX_CAP);
+    }
+}
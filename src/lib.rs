@@ -24,13 +24,33 @@ pub mod data {
         pub mod bank;
         pub mod equipment;
         pub mod items;
+        pub mod ledger;
+        pub mod loot;
+        pub mod market;
+        pub mod storage;
+        pub mod tools;
         pub mod weapons;
+        pub mod weight;
     }
     pub mod achievements;
+    pub mod attributes;
+    pub mod catalog;
+    pub mod classes;
+    pub mod clock;
+    pub mod consumables;
+    pub mod crafting;
+    pub mod farming;
     pub mod guilds;
     pub mod health;
+    pub mod hunting;
+    pub mod mods;
     pub mod player;
+    pub mod quests;
     pub mod settings;
+    pub mod smithing;
+    pub mod statistics;
+    pub mod thieving;
+    pub mod work;
     pub mod xp;
 }
 
@@ -10,5 +10,6 @@ fn main() {
         panic_menu!("This program does not support {}.", os);
     }
 
+    albion_terminal_rpg::data::catalog::Catalog::load();
     albion_terminal_rpg::menus::accounts::main();
 }
@@ -1,5 +1,8 @@
 use crate::{
-    data::{player::Player, xp::XPType},
+    data::{
+        player::Player,
+        xp::{XPType, XP},
+    },
     prelude::{error, generic_prompt, page_header, select, success, unreachable, Instructions},
 };
 
@@ -23,6 +26,8 @@ pub fn main(player: &mut Player) {
                 "5. Mining",
                 "6. Smithing",
                 "7. Thieving",
+                "8. Farming",
+                "9. Hunting",
                 "NAV: Go Back",
             ],
             None,
@@ -38,7 +43,9 @@ pub fn main(player: &mut Player) {
             4 => xp_type = XPType::Mining,
             5 => xp_type = XPType::Smithing,
             6 => xp_type = XPType::Thieving,
-            7 => super::d1_developer_menu::main(player),
+            7 => xp_type = XPType::Farming,
+            8 => xp_type = XPType::Hunting,
+            9 => super::d1_developer_menu::main(player),
             _ => unreachable(),
         }
 
@@ -63,7 +70,7 @@ pub fn main(player: &mut Player) {
         };
 
         let result = match operation {
-            Operation::Add => player.xp.add(xp_type, amount),
+            Operation::Add => XP::add(player, xp_type, amount),
             Operation::Subtract => player.xp.subtract(xp_type, amount),
         };
 
@@ -75,7 +75,7 @@ fn armor_manager(player: &mut Player) {
 
 fn add_item(player: &mut Player) {
     if let Ok((flag, quantity)) = items::Inventory::build_transaction() {
-        let result = items::Inventory::buy(player, flag, quantity, false);
+        let result = items::Inventory::buy(player, &flag, quantity, false);
 
         match result {
             Ok(()) => {
@@ -90,7 +90,7 @@ fn add_item(player: &mut Player) {
 
 fn subtract_item(player: &mut Player) {
     if let Ok((flag, quantity)) = items::Inventory::build_transaction() {
-        let sell_result = items::Inventory::sell(player, flag, quantity, false);
+        let sell_result = items::Inventory::sell(player, &flag, quantity, false);
 
         match sell_result {
             Ok(()) => {
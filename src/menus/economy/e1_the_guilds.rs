@@ -1,14 +1,26 @@
 #![allow(unused_assignments, unused_variables, unused_mut)]
 use crate::{
+    combat::{
+        battle::Battle,
+        enemy::{Data, Enemies},
+    },
     data::{
+        farming::{Crop, Farm},
         guilds::{Guild, Guilds},
-        inventory::items,
+        hunting::{self, Hunt},
+        inventory::{armor, items, weapons},
+        smithing::{self, Gear},
+        thieving::{self, Caught, Target, Theft},
+        work::{Shift, Work},
         xp::{XPType, XP},
     },
-    prelude::{error, failure, page_header, random_num, select, success, unreachable, Instructions},
+    prelude::{
+        cancel, confirm, csv_table, error, failure, page_header, pause, select, success, unreachable, Instructions,
+    },
 };
 
 use crate::data::player::Player;
+use strum::IntoEnumIterator;
 
 pub fn main(player: &mut Player) {
     page_header("The Guilds", &Instructions::Keyboard);
@@ -21,7 +33,10 @@ pub fn main(player: &mut Player) {
             "4. Guild: Mining",
             "5. Guild: Smithing",
             "6. Guild: Thieving",
-            "7. Membership Shop",
+            "7. Guild: Farming",
+            "8. Guild: Hunting",
+            "9. Quest Boards",
+            "10. Membership Shop",
             "NAV: Go Back",
         ],
         None,
@@ -34,36 +49,23 @@ pub fn main(player: &mut Player) {
         3 => deter_non_members(player, Guild::Mining),
         4 => deter_non_members(player, Guild::Smithing),
         5 => deter_non_members(player, Guild::Thieving),
+        6 => deter_non_members(player, Guild::Farming),
+        7 => deter_non_members(player, Guild::Hunting),
         _ => {}
     }
 
     match guild_choice {
-        0 => guild_menu(player, "Fishing", XPType::Fishing, items::GuildTypes::Fish, None),
-        1 => guild_menu(
-            player,
-            "Cooking",
-            XPType::Cooking,
-            items::GuildTypes::Food,
-            Some(items::GuildTypes::Fish),
-        ),
-        2 => guild_menu(
-            player,
-            "Woodcutting",
-            XPType::Woodcutting,
-            items::GuildTypes::Wood,
-            None,
-        ),
-        3 => guild_menu(player, "Mining", XPType::Mining, items::GuildTypes::Ore, None),
-        4 => guild_menu(
-            player,
-            "Smithing",
-            XPType::Smithing,
-            items::GuildTypes::Ingots,
-            Some(items::GuildTypes::Ore),
-        ),
-        5 => guild_menu(player, "Thieving", XPType::Thieving, items::GuildTypes::Gold, None),
-        6 => guild_membership_shop(player),
-        7 => crate::menus::game_menu::main(player),
+        0 => guild_menu(player, Guild::Fishing),
+        1 => guild_menu(player, Guild::Cooking),
+        2 => guild_menu(player, Guild::Woodcutting),
+        3 => guild_menu(player, Guild::Mining),
+        4 => smithy(player),
+        5 => thieves_den(player),
+        6 => farm(player),
+        7 => hunting_grounds(player),
+        8 => quest_boards(player),
+        9 => guild_membership_shop(player),
+        10 => crate::menus::game_menu::main(player),
         _ => unreachable(),
     }
 }
@@ -75,118 +77,507 @@ fn deter_non_members(player: &mut Player, guild: Guild) {
     }
 }
 
-fn guild_menu(
-    player: &mut Player,
-    guild: &str,
-    xp_type: XPType,
-    increase_item: items::GuildTypes,
-    decrease_item: Option<items::GuildTypes>,
-) {
+fn guild_menu(player: &mut Player, guild: Guild) {
+    let Some(work) = Work::of(guild) else {
+        unreachable();
+        return;
+    };
+
     loop {
-        print_guild_information(guild, player, xp_type, increase_item, decrease_item);
+        player.announce_level_ups();
+
+        print_guild_information(player, guild, &work);
         let work_choice = select(&["Work", "NAV: Go Back"], None);
 
         match work_choice {
-            0 => {
-                if let Some(item) = decrease_item {
-                    let result: error::Result<()> = match item {
-                        items::GuildTypes::Gold => {
-                            let rand = random_num(1, 3);
-
-                            if player.bank.wallet < rand {
-                                player.bank.wallet = 0;
-                            } else {
-                                player.bank.wallet -= rand;
-                            }
-                            Ok(())
-                        }
-                        items::GuildTypes::Bait => try_subtract(&mut player.items.bait, "Bait"),
-                        items::GuildTypes::Food => try_subtract(&mut player.items.food, "Cooked Fish"),
-                        items::GuildTypes::Fish => try_subtract(&mut player.items.fish, "Fish"),
-                        items::GuildTypes::Wood => try_subtract(&mut player.items.wood, "Wood"),
-                        items::GuildTypes::Ingots => try_subtract(&mut player.items.ingots, "Ingots"),
-                        items::GuildTypes::Ore => try_subtract(&mut player.items.ore, "Ore"),
-                    };
-
-                    if let Err(error) = result {
-                        error.print(true);
-                        guild_menu(player, guild, xp_type, increase_item, decrease_item);
+            0 => match work.shift(player) {
+                Ok(Shift::Success {
+                    produced,
+                    rare,
+                    tool_broke,
+                }) => {
+                    println!("You earned {produced} {}.", work.produce);
+
+                    if let Some(item) = rare {
+                        println!("Rare find! You found 1 {item}.");
+                    }
+
+                    if tool_broke {
+                        println!("Your tool broke.");
                     }
-                }
 
-                match increase_item {
-                    items::GuildTypes::Gold => player.bank.wallet += random_num(0, 2),
-                    items::GuildTypes::Bait => player.items.bait += 1,
-                    items::GuildTypes::Food => player.items.food += 1,
-                    items::GuildTypes::Fish => player.items.fish += 1,
-                    items::GuildTypes::Wood => player.items.wood += 1,
-                    items::GuildTypes::Ingots => player.items.ingots += 1,
-                    items::GuildTypes::Ore => player.items.ore += 1,
+                    pause();
                 }
+                Ok(Shift::Failed { tool_broke }) => {
+                    if tool_broke {
+                        println!("Your tool broke.");
+                    }
 
-                player.xp.increment(xp_type);
+                    failure("Your work came to nothing this time.");
+                }
+                Err(message) => message.print(true),
+            },
+            1 => {
+                player.save();
+                main(player);
             }
-            1 => main(player),
             _ => unreachable(),
         }
     }
 }
 
-fn print_guild_information(
-    name: &str,
-    player: &mut Player,
-    xp_type: XPType,
-    increase_item: items::GuildTypes,
-    decrease_item: Option<items::GuildTypes>,
-) {
-    page_header(format!("Guild: {name}"), &Instructions::Keyboard);
+fn print_guild_information(player: &mut Player, guild: Guild, work: &Work) {
+    page_header(format!("Guild: {guild}"), &Instructions::Keyboard);
 
-    let xp = player.xp.get(xp_type);
+    let xp: usize = *player.xp.get(work.skill);
+    let level: usize = XP::get_level(xp);
 
     println!("XP: {xp}");
-    println!("Level: {}", XP::get_level(*xp));
+    println!("Level: {level}");
+    println!("Success Chance: {}%", Work::success_chance(level));
+    println!("Yield: 1-{} {}", Work::max_yield(level), work.produce);
+
+    if let Some(item) = work.rare {
+        println!("Rare Find: {item} ({}%)", Work::rare_chance(level));
+    }
+
     println!();
 
-    print_item(player, Some(increase_item));
-    print_item(player, decrease_item);
+    if let Some(tool) = work.tool {
+        match player.tools.durability.get(&tool) {
+            Some(durability) => println!("{tool}: {durability}/{}", tool.durability()),
+            None => println!("{tool}: Not Owned"),
+        }
+    }
+
+    for (item, quantity) in work.inputs {
+        println!("{item}: {} (Uses {quantity})", player.items.get(item));
+    }
+
+    println!("{}: {}", work.produce, player.items.get(work.produce));
+
     println!();
 }
 
-fn print_item(player: &mut Player, item: Option<items::GuildTypes>) {
-    if let Some(item) = item {
-        match item {
-            items::GuildTypes::Gold => {
-                println!("Gold: {}", player.bank.wallet);
+fn smithy(player: &mut Player) {
+    loop {
+        player.announce_level_ups();
+
+        page_header("Guild: Smithing", &Instructions::Keyboard);
+
+        let choice = select(
+            &[
+                "1. Smelt Ore",
+                "2. Upgrade Equipment",
+                "3. Repair Equipment",
+                "NAV: Go Back",
+            ],
+            None,
+        );
+
+        match choice {
+            0 => guild_menu(player, Guild::Smithing),
+            1 => upgrade_equipment(player),
+            2 => repair_equipment(player),
+            3 => main(player),
+            _ => unreachable(),
+        }
+    }
+}
+
+fn farm(player: &mut Player) {
+    loop {
+        player.announce_level_ups();
+
+        page_header("Guild: Farming", &Instructions::Keyboard);
+
+        println!("{}", player.clock);
+        println!("XP: {}", player.xp.farming);
+        println!("Level: {}", XP::get_level(player.xp.farming));
+        println!("Seeds: {}", player.items.get(items::Types::Seeds));
+        println!();
+
+        player.farm.table(player.clock.hours);
+
+        let choice = select(&["1. Plant", "2. Harvest", "3. Tend Crops", "NAV: Go Back"], None);
+
+        let result: error::Result<()> = match choice {
+            0 => {
+                let plot: usize = select_plot(player);
+                let crops: Vec<Crop> = Crop::iter().collect();
+                let names: Vec<String> = crops
+                    .iter()
+                    .map(|crop| {
+                        format!(
+                            "{crop} ({} Seeds, {} Hours, Level {})",
+                            crop.seeds(),
+                            crop.growth_hours(),
+                            crop.level()
+                        )
+                    })
+                    .collect();
+
+                Farm::plant(player, plot, crops[select(&names, None)])
             }
-            items::GuildTypes::Bait => {
-                println!("Bait: {}", player.items.bait);
+            1 => {
+                let plot: usize = select_plot(player);
+
+                match Farm::harvest(player, plot) {
+                    Ok((crop, harvested)) => {
+                        player.save();
+                        success(Some(&format!("Harvested {harvested} {}.", crop.produce())));
+                        continue;
+                    }
+                    Err(message) => Err(message),
+                }
             }
-            items::GuildTypes::Food => {
-                println!("Cooked Fish: {}", player.items.food);
+            2 => {
+                XP::increment(player, XPType::Farming);
+                player.pass_time(1);
+                Ok(())
             }
-            items::GuildTypes::Fish => {
-                println!("Fish: {}", player.items.fish);
+            3 => {
+                player.save();
+                main(player);
+                return;
             }
-            items::GuildTypes::Wood => {
-                println!("Wood: {}", player.items.wood);
+            _ => {
+                unreachable();
+                return;
             }
-            items::GuildTypes::Ingots => {
-                println!("Ingots: {}", player.items.ingots);
+        };
+
+        match result {
+            Ok(()) => player.save(),
+            Err(message) => message.print(true),
+        }
+    }
+}
+
+fn hunting_grounds(player: &mut Player) {
+    loop {
+        player.announce_level_ups();
+
+        page_header("Guild: Hunting", &Instructions::Keyboard);
+
+        let level: usize = XP::get_level(player.xp.hunting);
+
+        println!("XP: {}", player.xp.hunting);
+        println!("Level: {level}");
+        println!("Furs: {}", player.items.get(items::Types::Furs));
+        println!("Raw Meat: {}", player.items.get(items::Types::Meat));
+        println!("Trap Chance: {}%", hunting::success_chance(level));
+        println!();
+
+        let mut choices: Vec<String> = hunting::QUARRY
+            .iter()
+            .map(|animal| {
+                format!(
+                    "Hunt {animal} (Level {}, {}% Danger)",
+                    hunting::level(*animal),
+                    hunting::danger(*animal, level)
+                )
+            })
+            .collect();
+        choices.push("NAV: Go Back".to_string());
+
+        let choice = select(&choices, None);
+
+        let Some(animal) = hunting::QUARRY.get(choice).copied() else {
+            player.save();
+            main(player);
+            return;
+        };
+
+        match hunting::hunt(player, animal) {
+            Ok(Hunt::Caught { furs, meat }) => {
+                success(Some(&format!(
+                    "You trapped the {animal}, taking {furs} fur and {meat} meat."
+                )));
             }
-            items::GuildTypes::Ore => {
-                println!("Ores: {}", player.items.ore);
+            Ok(Hunt::Escaped) => failure(&format!("The {animal} escaped.")),
+            Ok(Hunt::Ambushed(animal)) => {
+                let mut battle = Battle::new("Hunting Grounds", "The hunt has turned on you...", player, 0);
+
+                battle.enemy = Data::species(animal, battle.player.xp.combat, battle.player.health.hp);
+                battle.enemy.rewards.extend(hunting::battle_rewards(animal));
+                battle.start();
             }
+            Err(message) => message.print(true),
         }
+
+        player.save();
     }
 }
 
-fn try_subtract(item: &mut usize, item_name: &str) -> error::Result<()> {
-    if *item == 0 {
-        return Err(Box::new(error::Inventory::NotEnoughItem(item_name.to_string())));
+fn thieves_den(player: &mut Player) {
+    let targets: Vec<Target> = Target::iter().collect();
+
+    loop {
+        player.announce_level_ups();
+
+        page_header("Guild: Thieving", &Instructions::Keyboard);
+
+        let level: usize = XP::get_level(player.xp.thieving);
+
+        println!("XP: {}", player.xp.thieving);
+        println!("Level: {level}");
+        println!("Gold: {}", player.bank.wallet);
+        println!("Bounty: {}", player.bounty);
+        println!("Shop Prices: +{}%", thieving::markup_percent(player));
+        println!();
+
+        let mut strings: Vec<String> = vec!["Target,Level,Chance,Loot,Bounty if Caught".to_string()];
+
+        for target in &targets {
+            let (min, max) = target.loot();
+
+            strings.push(format!(
+                "{target},{},{}%,{min}-{max} Gold,{}",
+                target.level(),
+                thieving::success_chance(player, *target),
+                target.bounty()
+            ));
+        }
+
+        csv_table(&strings);
+
+        let mut choices: Vec<String> = targets.iter().map(ToString::to_string).collect();
+        choices.push("Pay Bounty".to_string());
+        choices.push("NAV: Go Back".to_string());
+
+        let choice = select(&choices, None);
+
+        if choice == targets.len() {
+            match thieving::pay_bounty(player) {
+                Ok(paid) => success(Some(&format!("Paid {paid} gold toward your bounty."))),
+                Err(message) => message.print(true),
+            }
+
+            player.save();
+            continue;
+        }
+
+        let Some(target) = targets.get(choice).copied() else {
+            player.save();
+            main(player);
+            return;
+        };
+
+        match thieving::steal(player, target) {
+            Ok(Theft::Success {
+                gold,
+                valuable,
+                spotted,
+            }) => {
+                println!("You got away with {gold} gold.");
+
+                if let Some(item) = valuable {
+                    println!("You also took 1 {item}.");
+                }
+
+                if spotted {
+                    println!("Someone saw your face. Your bounty has gone up.");
+                }
+
+                pause();
+            }
+            Ok(Theft::Caught(Caught::Fined(fine))) => {
+                failure(&format!(
+                    "You were caught and fined {fine} gold. Your bounty has gone up."
+                ));
+            }
+            Ok(Theft::Caught(Caught::Jailed(hours))) => {
+                failure(&format!(
+                    "You were caught and spent {hours} hours in jail. Your bounty has gone up."
+                ));
+            }
+            Ok(Theft::Caught(Caught::Guards)) => {
+                let mut battle = Battle::new("Caught by the Guards", "The guards have you cornered...", player, 0);
+
+                battle.enemy = Data::species(Enemies::Human, battle.player.xp.combat, battle.player.health.hp);
+                battle.enemy.name = "Town Guard".to_string();
+                battle.start();
+            }
+            Err(message) => message.print(true),
+        }
+
+        player.save();
+    }
+}
+
+fn select_plot(player: &Player) -> usize {
+    let plots: Vec<String> = (1..=player.farm.plots.len())
+        .map(|plot| format!("Plot {plot}"))
+        .collect();
+
+    select(&plots, None)
+}
+
+fn upgrade_equipment(player: &mut Player) {
+    page_header("Guild: Smithing - Upgrades", &Instructions::Keyboard);
+
+    player.weapons.table();
+    player.armor.table();
+
+    let category = select(&["1. Upgrade Weapon", "2. Upgrade Armor", "NAV: Go Back"], None);
+
+    let gear: Gear = match category {
+        0 => Gear::Weapon(weapons::Inventory::select()),
+        1 => Gear::Armor(armor::Inventory::select()),
+        _ => return,
+    };
+
+    let target: usize = match smithing::upgrade_level(player, &gear) {
+        Ok(level) => level + 1,
+        Err(message) => {
+            message.print(true);
+            return;
+        }
+    };
+
+    println!("Upgrading to +{target} requires:");
+
+    for (flag, quantity) in smithing::materials(target) {
+        println!("- {flag}: {quantity} (Owned: {})", player.items.get(flag));
+    }
+
+    let chance = smithing::player_success_chance(player, target);
+
+    println!();
+    println!("Success Chance: {chance}%");
+    println!("Materials are lost if the upgrade fails.");
+    println!();
+
+    if !confirm("Attempt the upgrade?") {
+        cancel(None);
+        return;
     }
 
-    *item -= 1;
-    Ok(())
+    match smithing::upgrade(player, &gear) {
+        Ok(true) => success(Some(&format!("Your equipment is now +{target}."))),
+        Ok(false) => failure("The upgrade failed."),
+        Err(message) => message.print(true),
+    }
+
+    player.save();
+}
+
+fn repair_equipment(player: &mut Player) {
+    page_header("Guild: Smithing - Repairs", &Instructions::Keyboard);
+
+    player.weapons.table();
+    player.armor.table();
+
+    let category = select(&["1. Repair Weapon", "2. Repair Armor", "NAV: Go Back"], None);
+
+    let gear: Gear = match category {
+        0 => Gear::Weapon(weapons::Inventory::select()),
+        1 => Gear::Armor(armor::Inventory::select()),
+        _ => return,
+    };
+
+    let ingots: usize = match smithing::missing_durability(player, &gear) {
+        Ok(missing) => smithing::repair_materials(missing),
+        Err(message) => {
+            message.print(true);
+            return;
+        }
+    };
+
+    if !confirm(&format!(
+        "Repairing this costs {ingots} ingot(s). You have {}. Continue?",
+        player.items.get(items::Types::Ingots)
+    )) {
+        cancel(None);
+        return;
+    }
+
+    match smithing::repair(player, &gear) {
+        Ok(()) => success(None),
+        Err(message) => message.print(true),
+    }
+
+    player.save();
+}
+
+fn quest_boards(player: &mut Player) {
+    let joined: Vec<Guild> = player.guilds.memberships.keys().copied().collect();
+
+    if joined.is_empty() {
+        failure("Join a guild to see its quest board.");
+        main(player);
+        return;
+    }
+
+    page_header("Guild Quest Boards", &Instructions::Keyboard);
+
+    let mut choices: Vec<String> = joined.iter().map(ToString::to_string).collect();
+    choices.push("NAV: Go Back".to_string());
+
+    let Some(guild) = joined.get(select(&choices, None)).copied() else {
+        main(player);
+        return;
+    };
+
+    quest_board(player, guild);
+}
+
+fn quest_board(player: &mut Player, guild: Guild) {
+    loop {
+        player.announce_level_ups();
+
+        page_header(format!("Guild: {guild} - Quest Board"), &Instructions::Keyboard);
+
+        let Some(membership) = player.guilds.get(guild).cloned() else {
+            main(player);
+            return;
+        };
+
+        println!("Rank: {}", membership.rank);
+        println!("Contracts Delivered: {}", membership.contracts);
+        println!("Discount: {}%", membership.rank.discount());
+
+        if let Some(next) = membership.rank.next() {
+            let (level, contracts) = next.requirements();
+            println!("Next Rank: {next} (Level {level}, {contracts} Contracts)");
+        }
+
+        println!();
+
+        let mut strings: Vec<String> = vec!["Contract,Reward,XP,Owned".to_string()];
+
+        for contract in &membership.board {
+            strings.push(format!("{contract},{}", player.items.quantity(&contract.item)));
+        }
+
+        csv_table(&strings);
+        println!("New contracts are posted every day.\n");
+
+        let mut choices: Vec<String> = (1..=membership.board.len())
+            .map(|contract| format!("Deliver Contract {contract}"))
+            .collect();
+        choices.push("NAV: Go Back".to_string());
+
+        let choice = select(&choices, None);
+
+        if choice == membership.board.len() {
+            quest_boards(player);
+            return;
+        }
+
+        match Guilds::deliver(player, guild, choice) {
+            Ok(promotion) => {
+                player.save();
+                success(Some("Contract delivered."));
+
+                if let Some(rank) = promotion {
+                    success(Some(&format!("You have been promoted to {rank} of the {guild} guild!")));
+                }
+            }
+            Err(message) => message.print(true),
+        }
+    }
 }
 
 fn guild_membership_shop(player: &mut Player) {
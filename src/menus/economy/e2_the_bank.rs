@@ -1,7 +1,200 @@
-use crate::data::inventory::bank::Bank;
+use crate::data::inventory::{
+    armor,
+    bank::{Bank, LOAN_INTEREST},
+    items,
+    ledger::Source,
+    storage::Storage,
+    weapons,
+};
 use crate::data::player::Player;
+use crate::prelude::{
+    cancel, confirm, error, failure, generic_prompt, page_header, pause, select, success, unreachable, Instructions,
+};
+use strum::IntoEnumIterator;
+
+/// Most recent transactions shown in the ledger.
+const LEDGER_PAGE: usize = 25;
 
 pub fn main(player: &mut Player) {
-    Bank::menu(player, false);
-    crate::menus::game_menu::main(player);
+    page_header("The Bank", &Instructions::Keyboard);
+
+    player.bank.table();
+    println!("Net Worth: {}\n", player.bank.net_worth());
+
+    let choice = select(
+        &[
+            "1. Accounts",
+            "2. Loans",
+            "3. Ledger",
+            "4. Vault Storage",
+            "NAV: Go Back",
+        ],
+        None,
+    );
+
+    match choice {
+        0 => Bank::menu(player, false),
+        1 => loans(player),
+        2 => ledger(player),
+        3 => storage(player),
+        4 => crate::menus::game_menu::main(player),
+        _ => unreachable(),
+    }
+}
+
+fn loans(player: &mut Player) {
+    loop {
+        page_header("The Bank - Loans", &Instructions::Keyboard);
+
+        println!("Wallet: {}", player.bank.wallet);
+        println!("Debt: {}", player.bank.debt);
+        println!("Available to Borrow: {}", player.bank.loan_limit());
+        println!("Interest: {LOAN_INTEREST}% of the debt per day");
+        println!();
+
+        let choice = select(&["1. Take a Loan", "2. Repay Loan", "NAV: Go Back"], None);
+
+        if choice == 2 {
+            player.save();
+            main(player);
+        }
+
+        let Ok(amount) = generic_prompt::<usize>("Amount") else {
+            pause();
+            continue;
+        };
+
+        match choice {
+            0 => {
+                if !confirm(&format!(
+                    "Borrow {amount} gold? Interest of {LOAN_INTEREST}% is added to the debt every day."
+                )) {
+                    cancel(None);
+                    continue;
+                }
+
+                match Bank::borrow(player, amount) {
+                    Ok(()) => success(None),
+                    Err(message) => message.print(true),
+                }
+            }
+            1 => match Bank::repay(player, amount) {
+                Ok(paid) => success(Some(&format!("Paid back {paid} gold."))),
+                Err(message) => message.print(true),
+            },
+            _ => unreachable(),
+        }
+    }
+}
+
+fn ledger(player: &mut Player) {
+    let sources: Vec<Source> = Source::iter().collect();
+    let mut filters: Vec<String> = vec!["All Transactions".to_string()];
+
+    filters.extend(sources.iter().map(ToString::to_string));
+
+    loop {
+        page_header("The Bank - Ledger", &Instructions::Keyboard);
+
+        let mut choices: Vec<String> = filters.clone();
+        choices.push("NAV: Go Back".to_string());
+
+        let choice = select(&choices, Some("Show transactions from"));
+
+        if choice == filters.len() {
+            main(player);
+        }
+
+        page_header("The Bank - Ledger", &Instructions::None);
+
+        player
+            .ledger
+            .table(sources.get(choice.wrapping_sub(1)).copied(), LEDGER_PAGE);
+        pause();
+    }
+}
+
+fn storage(player: &mut Player) {
+    loop {
+        page_header("The Bank - Vault Storage", &Instructions::Keyboard);
+
+        player.storage.table();
+
+        let choice = select(
+            &[
+                "1. Store Items",
+                "2. Store Weapon",
+                "3. Store Armor",
+                "4. Store Loot",
+                "5. Retrieve",
+                "6. Buy Slot",
+                "NAV: Go Back",
+            ],
+            None,
+        );
+
+        let result: error::Result<()> = match choice {
+            0 => {
+                let id = items::Inventory::select();
+
+                match generic_prompt::<usize>("Quantity") {
+                    Ok(quantity) => Storage::store_item(player, &id, quantity),
+                    Err(message) => Err(message),
+                }
+            }
+            1 => Storage::store_weapon(player, &weapons::Inventory::select()),
+            2 => Storage::store_armor(player, &armor::Inventory::select()),
+            3 => {
+                if player.loot.gear.is_empty() {
+                    failure("You do not have any loot.");
+                    continue;
+                }
+
+                let names: Vec<String> = player.loot.gear.iter().map(ToString::to_string).collect();
+                let id: usize = player.loot.gear[select(&names, None)].id;
+
+                Storage::store_loot(player, id)
+            }
+            4 => {
+                if player.storage.stored.is_empty() {
+                    failure("Your vault storage is empty.");
+                    continue;
+                }
+
+                let names: Vec<String> = player
+                    .storage
+                    .stored
+                    .iter()
+                    .map(|stored| stored.to_string().replace(',', ": "))
+                    .collect();
+
+                Storage::retrieve(player, select(&names, None))
+            }
+            5 => {
+                if !confirm(&format!("Buy another slot for {} gold?", player.storage.slot_price())) {
+                    cancel(None);
+                    continue;
+                }
+
+                Storage::buy_slot(player)
+            }
+            6 => {
+                player.save();
+                main(player);
+                return;
+            }
+            _ => {
+                unreachable();
+                return;
+            }
+        };
+
+        match result {
+            Ok(()) => {
+                player.save();
+                success(None);
+            }
+            Err(message) => message.print(true),
+        }
+    }
 }
@@ -1,6 +1,15 @@
+use strum::IntoEnumIterator;
+
 use crate::{
-    data::{inventory::items, player::Player},
-    prelude::{error::Printer, page_header, select, success, unreachable, Instructions},
+    data::{
+        inventory::{
+            items,
+            tools::{Tool, Tools},
+            weight::{self, BAG_CAPACITY},
+        },
+        player::Player,
+    },
+    prelude::{cancel, confirm, error::Printer, page_header, select, success, unreachable, Instructions},
 };
 
 pub fn main(player: &mut Player) {
@@ -8,14 +17,20 @@ pub fn main(player: &mut Player) {
         page_header("Trading Post", &Instructions::None);
 
         items::Inventory::shop_table(player);
-        println!("Gold: {}\n", player.bank.wallet);
+        println!("Gold: {}", player.bank.wallet);
+        println!("{}\n", weight::summary(player));
 
-        let buysell = select(&["1. Purchase", "2. Sell", "NAV: Go Back"], None);
+        let buysell = select(
+            &["1. Purchase", "2. Sell", "3. Buy Bag", "4. Buy Tools", "NAV: Go Back"],
+            None,
+        );
 
         match buysell {
             0 => purchase(player),
             1 => sell(player),
-            2 => crate::menus::game_menu::main(player),
+            2 => buy_bag(player),
+            3 => buy_tools(player),
+            4 => crate::menus::game_menu::main(player),
             _ => unreachable(),
         }
     }
@@ -24,7 +39,7 @@ pub fn main(player: &mut Player) {
 pub fn purchase(player: &mut Player) {
     match items::Inventory::build_transaction() {
         Ok((item_flag, quantity)) => {
-            let bought = items::Inventory::buy(player, item_flag, quantity, true);
+            let bought = items::Inventory::buy(player, &item_flag, quantity, true);
 
             match bought {
                 Ok(()) => {
@@ -44,7 +59,7 @@ pub fn purchase(player: &mut Player) {
 pub fn sell(player: &mut Player) {
     match items::Inventory::build_transaction() {
         Ok((item_flag, quantity)) => {
-            let sold = items::Inventory::sell(player, item_flag, quantity, true);
+            let sold = items::Inventory::sell(player, &item_flag, quantity, true);
 
             match sold {
                 Ok(()) => {
@@ -60,3 +75,42 @@ pub fn sell(player: &mut Player) {
         }
     }
 }
+
+pub fn buy_bag(player: &mut Player) {
+    let price: usize = weight::bag_price(player);
+
+    if !confirm(&format!(
+        "Buy a bag that holds {BAG_CAPACITY} more weight for {price} gold?"
+    )) {
+        cancel(None);
+        return;
+    }
+
+    match weight::buy_bag(player) {
+        Ok(()) => success(None),
+        Err(message) => message.print(true),
+    }
+}
+
+pub fn buy_tools(player: &mut Player) {
+    page_header("Trading Post - Tools", &Instructions::None);
+
+    Tools::shop_table(player);
+    println!("Gold: {}\n", player.bank.wallet);
+
+    let tools: Vec<Tool> = Tool::iter().collect();
+    let mut choices: Vec<String> = tools.iter().map(ToString::to_string).collect();
+    choices.push("NAV: Go Back".to_string());
+
+    let Some(tool) = tools.get(select(&choices, None)).copied() else {
+        return;
+    };
+
+    match Tools::buy(player, tool) {
+        Ok(()) => {
+            player.save();
+            success(None);
+        }
+        Err(message) => message.print(true),
+    }
+}
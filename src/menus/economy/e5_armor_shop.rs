@@ -1,5 +1,5 @@
 use crate::data::inventory::armor;
-use crate::prelude::{error::Printer, page_header, select, success, unreachable, Instructions};
+use crate::prelude::{cancel, confirm, error::Printer, page_header, select, success, unreachable, Instructions};
 
 use crate::data::player::Player;
 
@@ -9,12 +9,13 @@ pub fn main(player: &mut Player) {
 
         armor::Inventory::shop_table(player);
 
-        let buysell = select(&["1. Purchase", "2. Sell", "NAV: Go Back"], None);
+        let buysell = select(&["1. Purchase", "2. Sell", "3. Repair", "NAV: Go Back"], None);
 
         match buysell {
             0 => purchase(player),
             1 => sell(player),
-            2 => crate::menus::game_menu::main(player),
+            2 => repair(player),
+            3 => crate::menus::game_menu::main(player),
             _ => unreachable(),
         }
     }
@@ -47,3 +48,29 @@ pub fn sell(player: &mut Player) {
         }
     }
 }
+
+pub fn repair(player: &mut Player) {
+    let flag = armor::Inventory::select();
+
+    let price = match armor::Inventory::repair_price(player, &flag) {
+        Ok(price) => price,
+        Err(message) => {
+            message.print(true);
+            return;
+        }
+    };
+
+    if !confirm(&format!("Repairing the {flag} costs {price} gold. Continue?")) {
+        cancel(None);
+        return;
+    }
+
+    match armor::Inventory::repair(player, &flag, true) {
+        Ok(()) => {
+            success(None);
+        }
+        Err(message) => {
+            message.print(true);
+        }
+    }
+}
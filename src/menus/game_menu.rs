@@ -1,7 +1,15 @@
 use crate::{
-    combat::battle::Battle,
-    data::{achievements::Achievements, inventory::equipment::Equipment, settings::Settings},
-    prelude::{confirm, exit, invalid_input, page_header, pause, prompt, sleep, success, Instructions},
+    combat::{
+        battle::{Battle, BattleOutcome},
+        enemy::Data,
+    },
+    data::{
+        achievements::Achievements, catalog::Catalog, inventory::equipment::Equipment, quests::Quests,
+        settings::Settings, xp::XP,
+    },
+    prelude::{
+        confirm, exit, failure, invalid_input, page_header, pause, prompt, select, sleep, success, Instructions,
+    },
     utils::terminal::STANDARD_SLEEP,
 };
 
@@ -13,6 +21,8 @@ fn print_menu(player: &mut Player) {
         &Instructions::TypeCode,
     );
 
+    println!("{}\n", player.clock);
+
     println!("#------- Combat ------#");
     println!("1. Wander the Realm");
     println!("2. Enter the Stronghold");
@@ -44,6 +54,11 @@ fn print_menu(player: &mut Player) {
 
 pub fn main(player: &mut Player) {
     loop {
+        // Count time played whenever the player returns to the main menu
+        player.statistics.tick();
+
+        player.announce_level_ups();
+
         // Check for achievements at login to keep the player file up to date
         Achievements::check(player);
 
@@ -56,9 +71,7 @@ pub fn main(player: &mut Player) {
 
         match &choice[..] {
             // Combat
-            "1" | "wander the realm" => {
-                Battle::new("Wandering the Wild", "You are wandering the realm...", player, 0, None).start();
-            }
+            "1" | "wander the realm" => wander(player),
             "2" | "enter the stronghold" => {
                 page_header("The Stronghold", &Instructions::None);
 
@@ -66,14 +79,13 @@ pub fn main(player: &mut Player) {
                     confirm("Are you sure you want to enter the stronghold? You must win many hard battles.");
 
                 if enter_stronghold {
-                    Battle::new(
-                        "The Stronghold",
-                        "You delve into the stronghold...",
-                        player,
-                        50,
-                        Some(exit_stronghold),
-                    )
-                    .start();
+                    let outcome = Battle::new("The Stronghold", "You delve into the stronghold...", player, 50).start();
+
+                    match outcome {
+                        BattleOutcome::Victory(_) => exit_stronghold(player),
+                        BattleOutcome::FloorReached(floor) => retreat_stronghold(floor),
+                        BattleOutcome::Defeat | BattleOutcome::Retreat => {}
+                    }
                 }
             }
 
@@ -97,6 +109,7 @@ pub fn main(player: &mut Player) {
                 success(None);
             }
             "99" | "logout" => {
+                player.statistics.tick();
                 player.save();
 
                 page_header("Accounts Menu", &Instructions::None);
@@ -107,6 +120,7 @@ pub fn main(player: &mut Player) {
             }
 
             "exit" => {
+                player.statistics.tick();
                 exit(Some(player));
             }
 
@@ -132,6 +146,79 @@ pub fn main(player: &mut Player) {
     }
 }
 
+fn wander(player: &mut Player) {
+    page_header("Wander the Realm", &Instructions::Keyboard);
+
+    let regions = &Catalog::get().regions;
+
+    let mut choices: Vec<String> = vec!["1. Wander the Wild".to_string()];
+    choices.extend(
+        regions
+            .iter()
+            .enumerate()
+            .map(|(index, region)| format!("{}. {} (Combat Level {})", index + 2, region.name, region.level)),
+    );
+    choices.push(format!("{}. Quest Log", regions.len() + 2));
+    choices.push("NAV: Go Back".to_string());
+
+    let choice = select(&choices, None);
+
+    if choice == 0 {
+        Battle::new("Wandering the Wild", "You are wandering the realm...", player, 0).start();
+        return;
+    }
+
+    if choice == regions.len() + 1 {
+        quest_log(player);
+        return;
+    }
+
+    let Some(region) = regions.get(choice - 1) else {
+        return;
+    };
+
+    if XP::get_level(player.xp.combat) < region.level {
+        failure(&format!(
+            "You need combat level {} to enter {}.",
+            region.level, region.name
+        ));
+        return;
+    }
+
+    let mut battle = Battle::new(&region.name, "You are exploring the region...", player, 0);
+    battle.enemy = Data::in_region(region, battle.player.xp.combat, battle.player.health.hp);
+    battle.start();
+}
+
+fn quest_log(player: &mut Player) {
+    loop {
+        page_header("Quest Log", &Instructions::Keyboard);
+
+        Quests::table(player);
+        println!();
+
+        let quests = &Catalog::get().quests;
+
+        let mut choices: Vec<String> = quests
+            .iter()
+            .map(|quest| format!("Take Quest: {}", quest.name))
+            .collect();
+        choices.push("NAV: Go Back".to_string());
+
+        let Some(quest) = quests.get(select(&choices, None)) else {
+            return;
+        };
+
+        match Quests::accept(player, &quest.id) {
+            Ok(()) => {
+                player.save();
+                success(Some(&format!("You took the quest {}.", quest.name)));
+            }
+            Err(message) => message.print(true),
+        }
+    }
+}
+
 fn exit_stronghold(player: &mut Player) {
     page_header("The Stronghold", &Instructions::None);
 
@@ -139,6 +226,15 @@ fn exit_stronghold(player: &mut Player) {
     player.achievements.stronghold_defeated = true;
     player.save();
 
+    Achievements::check(player);
+
+    pause();
+}
+
+fn retreat_stronghold(floor: usize) {
+    page_header("The Stronghold", &Instructions::None);
+
+    println!("\nYou escaped the stronghold after clearing {floor} floor(s).");
+
     pause();
-    main(player);
 }
@@ -1,4 +1,4 @@
-use crate::prelude::{error, failure, page_header, password, prompt, success, verify_hash, Instructions};
+use crate::prelude::{error, failure, page_header, password, prompt, success, verify_hash, warning, Instructions};
 
 use crate::data::player::Player;
 
@@ -30,6 +30,17 @@ pub fn main() {
 
             success(None);
 
+            let missing_packs = player.missing_packs();
+
+            if !missing_packs.is_empty() {
+                let names: Vec<&str> = missing_packs.iter().map(|pack| pack.as_str()).collect();
+
+                warning(Some(&format!(
+                    "This profile was created with content packs that are not installed: {}",
+                    names.join(", ")
+                )));
+            }
+
             crate::menus::game_menu::main(&mut player);
         }
 
@@ -1,6 +1,11 @@
 use crate::{
-    data::inventory::{equipment::Equipment, items},
-    prelude::{page_header, pause, select, unreachable, Instructions},
+    combat::inventory::{food_menu, potion_menu},
+    data::{
+        catalog::Catalog,
+        crafting,
+        inventory::{equipment::Equipment, items, weight},
+    },
+    prelude::{page_header, pause, select, success, unreachable, Instructions},
 };
 
 use crate::data::player::Player;
@@ -9,7 +14,18 @@ pub fn main(player: &mut Player) {
     loop {
         page_header("Inventory Viewer", &Instructions::None);
 
-        let menu_option = select(&["1. Items", "2. Equipment", "3. Finances", "NAV: Go Back"], None);
+        let menu_option = select(
+            &[
+                "1. Items",
+                "2. Equipment",
+                "3. Finances",
+                "4. Crafting",
+                "5. Eat Food",
+                "6. Drink Potions",
+                "NAV: Go Back",
+            ],
+            None,
+        );
 
         match menu_option {
             0 => mundane_inventory(player),
@@ -17,7 +33,16 @@ pub fn main(player: &mut Player) {
                 Equipment::menu(player);
             }
             2 => finances(player),
-            3 => crate::menus::game_menu::main(player),
+            3 => crafting(player),
+            4 => {
+                food_menu(player);
+                player.save();
+            }
+            5 => {
+                potion_menu(player);
+                player.save();
+            }
+            6 => crate::menus::game_menu::main(player),
             _ => unreachable(),
         }
     }
@@ -27,6 +52,7 @@ pub fn mundane_inventory(player: &mut Player) {
     page_header("Items Inventory", &Instructions::None);
 
     items::Inventory::shop_table(player);
+    println!("{}\n", weight::summary(player));
 
     pause();
     main(player);
@@ -40,3 +66,34 @@ pub fn finances(player: &mut Player) {
     pause();
     main(player);
 }
+
+pub fn crafting(player: &mut Player) {
+    let recipes = &Catalog::get().recipes;
+    let mut choices: Vec<String> = recipes.iter().map(|recipe| recipe.name.clone()).collect();
+    choices.push("NAV: Go Back".to_string());
+
+    loop {
+        player.announce_level_ups();
+
+        page_header("Crafting", &Instructions::Keyboard);
+
+        crafting::table(player);
+        println!("{}\n", weight::summary(player));
+
+        let choice = select(&choices, Some("Craft"));
+
+        let Some(recipe) = recipes.get(choice) else {
+            player.save();
+            main(player);
+            return;
+        };
+
+        match crafting::craft(player, recipe) {
+            Ok(()) => {
+                player.save();
+                success(Some(&format!("Crafted {}.", crafting::output(recipe))));
+            }
+            Err(message) => message.print(true),
+        }
+    }
+}
@@ -1,19 +1,36 @@
 use crate::{
-    data::achievements::Achievements,
-    prelude::{page_header, pause, select, unreachable, Instructions},
+    data::{
+        achievements::Achievements,
+        attributes::{Attribute, Attributes},
+    },
+    prelude::{generic_prompt, page_header, pause, select, success, unreachable, Instructions},
 };
+use strum::IntoEnumIterator;
 
 use crate::data::player::Player;
 
 pub fn main(player: &mut Player) {
     page_header("Hall of Records", &Instructions::Keyboard);
 
-    let menu_option = select(&["1. XP/Levels", "2. Achievements", "NAV: Go Back"], None);
+    let menu_option = select(
+        &[
+            "1. XP/Levels",
+            "2. Achievements",
+            "3. Attributes",
+            "4. Class Skills",
+            "5. Statistics",
+            "NAV: Go Back",
+        ],
+        None,
+    );
 
     match menu_option {
         0 => xp(player),
         1 => achievements(player),
-        2 => crate::menus::game_menu::main(player),
+        2 => attributes(player),
+        3 => class(player),
+        4 => statistics(player),
+        5 => crate::menus::game_menu::main(player),
         _ => unreachable(),
     }
 }
@@ -32,14 +49,82 @@ pub fn achievements(player: &mut Player) {
     // printing them
     Achievements::check(player);
 
-    // Save the profile so new achievements are written to disk
-    player.save();
-
     // Print out all achievements
     page_header("Hall of Records - Achievements", &Instructions::None);
 
-    player.achievements.table();
+    Achievements::table(player);
 
     pause();
     main(player);
 }
+
+pub fn statistics(player: &mut Player) {
+    player.statistics.tick();
+
+    page_header("Hall of Records - Statistics", &Instructions::None);
+
+    println!("In-Game Time: {}\n", player.clock);
+    player.statistics.table();
+
+    println!("Kills");
+    player.statistics.kills_table();
+
+    println!("Items Gathered");
+    player.statistics.gathering_table();
+
+    println!("Potions Used");
+    player.statistics.potions_table();
+
+    pause();
+    main(player);
+}
+
+pub fn class(player: &mut Player) {
+    page_header(
+        format!("Hall of Records - {} Skills", player.class),
+        &Instructions::None,
+    );
+
+    println!("{}\n", player.class.description());
+    player.class.table(player);
+
+    pause();
+    main(player);
+}
+
+pub fn attributes(player: &mut Player) {
+    let attributes: Vec<Attribute> = Attribute::iter().collect();
+    let mut choices: Vec<String> = attributes
+        .iter()
+        .map(|attribute| format!("Raise {attribute}"))
+        .collect();
+    choices.push("NAV: Go Back".to_string());
+
+    loop {
+        page_header("Hall of Records - Attributes", &Instructions::Keyboard);
+
+        player.attributes.table();
+        println!("Max HP: {}", player.max_hp());
+        println!("Hit Chance: {}%", player.hit_chance());
+        println!("Unspent Stat Points: {}\n", player.xp.stat_points);
+
+        let Some(attribute) = attributes.get(select(&choices, None)).copied() else {
+            player.save();
+            main(player);
+            return;
+        };
+
+        let Ok(points) = generic_prompt::<usize>("Points") else {
+            pause();
+            continue;
+        };
+
+        match Attributes::allocate(player, attribute, points) {
+            Ok(()) => {
+                player.save();
+                success(None);
+            }
+            Err(message) => message.print(true),
+        }
+    }
+}
@@ -1,7 +1,8 @@
 use crate::{
-    data::player::Player,
-    prelude::{failure, generate_hash, page_header, password, prompt, success, Instructions},
+    data::{classes::Class, player::Player},
+    prelude::{failure, generate_hash, page_header, password, prompt, select, success, Instructions},
 };
+use strum::IntoEnumIterator;
 
 pub fn main() {
     page_header("Registration", &Instructions::None);
@@ -25,9 +26,25 @@ pub fn main() {
 
     let password_hash = generate_hash(&user_password);
 
-    let profile = Player::new(&username, &password_hash, true);
+    let class: Class = choose_class();
+
+    let mut profile = Player::new(&username, &password_hash, false);
+    profile.class = class;
 
     profile.save();
     success(None);
     crate::menus::accounts::main();
 }
+
+/// Pick the class for a new profile. It cannot be changed later.
+fn choose_class() -> Class {
+    page_header("Registration - Choose a Class", &Instructions::Keyboard);
+
+    let classes: Vec<Class> = Class::iter().collect();
+    let choices: Vec<String> = classes
+        .iter()
+        .map(|class| format!("{class}: {}", class.description()))
+        .collect();
+
+    classes[select(&choices, None)]
+}
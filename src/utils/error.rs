@@ -134,6 +134,9 @@ pub enum Inventory {
     NotEnoughItem(String),
     ItemNotExist,
     TransactionFailed,
+    MaxUpgrade,
+    NothingToRepair,
+    TooHeavy,
 }
 
 impl Display for Inventory {
@@ -149,6 +152,9 @@ impl Display for Inventory {
                 Self::NotEnoughItem(item) => format!("You do not own enough {item}."),
                 Self::NotEnoughXP => "You do not have enough xp.".to_string(),
                 Self::TransactionFailed => "Transaction failed.".to_string(),
+                Self::MaxUpgrade => "That item is already fully upgraded.".to_string(),
+                Self::NothingToRepair => "That item does not need repairs.".to_string(),
+                Self::TooHeavy => "You cannot carry that much weight.".to_string(),
             })
         )
     }
@@ -157,6 +163,89 @@ impl Display for Inventory {
 impl Printer for Inventory {}
 impl Error for Inventory {}
 
+/**
+Bank account, loan, and storage errors
+
+# Examples
+
+```panics
+use albion_terminal_rpg::prelude::error::Bank;
+
+fn main() -> Result<()> {
+    Bank::NoDebt.print(true);
+
+    Err(Bank::VaultLimit(500));
+}
+```
+*/
+#[derive(Debug, Clone)]
+pub enum Bank {
+    VaultLimit(usize),
+    LoanLimit(usize),
+    NoDebt,
+    InvalidAccount,
+    StorageFull,
+    StorageMaxed,
+}
+
+impl Display for Bank {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}",
+            Logs::Failure.paint(match self {
+                Self::VaultLimit(remaining) =>
+                    format!("You can only withdraw {remaining} more gold from the vault today."),
+                Self::LoanLimit(remaining) => format!("The bank will only lend you {remaining} more gold."),
+                Self::NoDebt => "You do not owe the bank anything.".to_string(),
+                Self::InvalidAccount => "That account does not support this.".to_string(),
+                Self::StorageFull => "Your vault storage is full. Buy another slot first.".to_string(),
+                Self::StorageMaxed => "Your vault storage cannot be expanded any further.".to_string(),
+            })
+        )
+    }
+}
+
+impl Printer for Bank {}
+impl Error for Bank {}
+
+/**
+Item catalog loading errors
+
+# Examples
+
+```panics
+use albion_terminal_rpg::prelude::error::Catalog;
+
+fn main() -> Result<()> {
+    Catalog::Parse("expected a table".to_string()).print(true);
+
+    Err(Catalog::Invalid(vec!["Missing core item 'bait'.".to_string()]));
+}
+```
+*/
+#[derive(Debug, Clone)]
+pub enum Catalog {
+    Parse(String),
+    Invalid(Vec<String>),
+}
+
+impl Display for Catalog {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}",
+            Logs::Failure.paint(match self {
+                Self::Parse(error) => format!("Failed to read the item catalog:\n{error}"),
+                Self::Invalid(problems) => format!("The item catalog is invalid:\n- {}", problems.join("\n- ")),
+            })
+        )
+    }
+}
+
+impl Printer for Catalog {}
+impl Error for Catalog {}
+
 /**
 File management Errors
 
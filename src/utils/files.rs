@@ -9,43 +9,78 @@ pub const FOLDER_NAME: &str = ".albion_term_rpg";
 pub const EXTENSION: &str = "albion";
 
 /**
-Generate the path for the folder containing player files.
+Generate the path for the game folder, which holds the profiles folder and
+any custom game content.
 
 # Example
 
 ```
-use albion_terminal_rpg::prelude::player_files_directory;
+use albion_terminal_rpg::prelude::game_directory;
 
-let dir = player_files_directory();
+let dir = game_directory();
 ```
 */
-pub fn player_files_directory() -> String {
+pub fn game_directory() -> String {
     match OS {
         "linux" | "freebsd" | "dragonfly" | "netbsd" | "openbsd" => {
-            Path::new(&format!("/home/{}/{}/profiles", whoami::username(), FOLDER_NAME))
+            Path::new(&format!("/home/{}/{}", whoami::username(), FOLDER_NAME))
                 .to_str()
                 .expect("Path could not be converted to string")
                 .to_string()
         }
 
-        "macos" => Path::new(&format!("/Users/{}/{}/profiles", whoami::username(), FOLDER_NAME))
+        "macos" => Path::new(&format!("/Users/{}/{}", whoami::username(), FOLDER_NAME))
             .to_str()
             .expect("Path could not be converted to string")
             .to_string(),
 
-        "windows" => Path::new(&format!(
-            r"C:\Users\{}\Documents\{}\profiles",
-            whoami::username(),
-            FOLDER_NAME
-        ))
-        .to_str()
-        .expect("Path could not be converted to string")
-        .to_string(),
+        "windows" => Path::new(&format!(r"C:\Users\{}\Documents\{}", whoami::username(), FOLDER_NAME))
+            .to_str()
+            .expect("Path could not be converted to string")
+            .to_string(),
 
         _ => panic_menu!("Empty path provided for directory."),
     }
 }
 
+/**
+Generate the path for the folder containing player files.
+
+# Example
+
+```
+use albion_terminal_rpg::prelude::player_files_directory;
+
+let dir = player_files_directory();
+```
+*/
+pub fn player_files_directory() -> String {
+    Path::new(&game_directory())
+        .join("profiles")
+        .to_str()
+        .expect("Path could not be converted to string")
+        .to_string()
+}
+
+/**
+Generate the path for the folder containing content packs.
+
+# Example
+
+```
+use albion_terminal_rpg::prelude::mods_directory;
+
+let dir = mods_directory();
+```
+*/
+pub fn mods_directory() -> String {
+    Path::new(&game_directory())
+        .join("mods")
+        .to_str()
+        .expect("Path could not be converted to string")
+        .to_string()
+}
+
 /**
 Generates the full path string for player data files.
 
@@ -198,6 +198,26 @@ pub fn checkmark(flag: bool) -> char {
     }
 }
 
+/**
+Draws a progress bar of a given width, followed by the percentage complete.
+
+# Examples
+
+```
+use albion_terminal_rpg::prelude::progress_bar;
+
+assert_eq!(progress_bar(1, 4, 8), "[##------] 25%");
+assert_eq!(progress_bar(9, 4, 8), "[########] 100%");
+```
+*/
+pub fn progress_bar(current: usize, goal: usize, width: usize) -> String {
+    let current: usize = current.min(goal);
+    let filled: usize = (current * width).checked_div(goal).unwrap_or(width);
+    let percent: usize = (current * 100).checked_div(goal).unwrap_or(100);
+
+    format!("[{}{}] {percent}%", "#".repeat(filled), "-".repeat(width - filled))
+}
+
 mod tests {
     #[test]
     fn line() {